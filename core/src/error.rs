@@ -35,4 +35,8 @@ pub enum ValidationError {
         _3
     )]
     InvalidParameterType(String, String, Option<DataType>, ParameterIn),
+    /// Operation IDs must be unique, as client generators use them for naming methods.
+    /// Each duplicate ID is listed along with the operations (method and path) using it.
+    #[error("Operation IDs are used by multiple operations: {:?}", _0)]
+    DuplicateOperationIds(Vec<(String, Vec<String>)>),
 }
@@ -7,8 +7,8 @@ extern crate actix_web_validator3 as actix_web_validator;
 use super::schema::TypedData;
 use super::{
     models::{
-        DefaultOperationRaw, DefaultSchemaRaw, Either, Items, Parameter, ParameterIn, Response,
-        SecurityScheme,
+        DataType, DataTypeFormat, DefaultOperationRaw, DefaultSchemaRaw, Either, Items, MediaRange,
+        Parameter, ParameterIn, ParameterStyle, Response, SecurityScheme,
     },
     schema::{Apiv2Errors, Apiv2Operation, Apiv2Schema},
 };
@@ -23,7 +23,7 @@ use actix_web::{body::BoxBody, ResponseError};
 use actix_web::{
     http::StatusCode,
     web::{Bytes, Data, Form, Json, Path, Payload, Query},
-    HttpRequest, HttpResponse, Responder,
+    Either as ActixEither, HttpRequest, HttpResponse, Responder,
 };
 
 use pin_project_lite::pin_project;
@@ -33,7 +33,7 @@ use actix_web_validator::{
     Json as ValidatedJson, Path as ValidatedPath, QsQuery as ValidatedQsQuery,
     Query as ValidatedQuery,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 #[cfg(feature = "serde_qs")]
 use serde_qs::actix::QsQuery;
 
@@ -47,6 +47,28 @@ use std::{
 
 /// Actix-specific trait for indicating that this entity can modify an operation
 /// and/or update the global map of definitions.
+///
+/// All the methods have defaults, so custom extractors (i.e., `FromRequest` types)
+/// can document whatever they read from the request by overriding the relevant ones.
+///
+/// ```ignore
+/// /// Extracts the tenant from the `X-Tenant-Id` header.
+/// struct Tenant(String);
+///
+/// impl Apiv2Schema for Tenant {}
+///
+/// impl OperationModifier for Tenant {
+///     fn update_parameter(op: &mut DefaultOperationRaw) {
+///         op.parameters.push(Either::Right(Parameter {
+///             in_: ParameterIn::Header,
+///             name: "X-Tenant-Id".into(),
+///             required: true,
+///             data_type: Some(DataType::String),
+///             ..Default::default()
+///         }));
+///     }
+/// }
+/// ```
 pub trait OperationModifier: Apiv2Schema + Sized {
     /// Update the parameters list in the given operation (if needed).
     fn update_parameter(op: &mut DefaultOperationRaw) {
@@ -168,6 +190,49 @@ where
     }
 }
 
+impl<L, R> Apiv2Schema for ActixEither<L, R> {}
+
+/// Either branch could be the response, so the responses of both branches
+/// are documented, with the schemas of conflicting status codes in a `oneOf`.
+impl<L, R> OperationModifier for ActixEither<L, R>
+where
+    L: OperationModifier,
+    R: OperationModifier,
+{
+    fn update_response(op: &mut DefaultOperationRaw) {
+        let mut right = DefaultOperationRaw::default();
+        R::update_response(&mut right);
+        L::update_response(op);
+
+        for (code, response) in right.responses {
+            match (op.responses.get_mut(&code), response) {
+                (Some(Either::Right(left)), Either::Right(right)) => {
+                    left.schema = match (left.schema.take(), right.schema) {
+                        (Some(l), Some(r))
+                            if l.reference.is_none() || l.reference != r.reference =>
+                        {
+                            Some(DefaultSchemaRaw {
+                                one_of: vec![Box::new(l), Box::new(r)],
+                                ..Default::default()
+                            })
+                        }
+                        (l, r) => l.or(r),
+                    };
+                }
+                (Some(_), _) => (),
+                (None, response) => {
+                    op.responses.insert(code, response);
+                }
+            }
+        }
+    }
+
+    fn update_definitions(map: &mut BTreeMap<String, DefaultSchemaRaw>) {
+        L::update_definitions(map);
+        R::update_definitions(map);
+    }
+}
+
 // We don't know what we should do with these abstractions
 // as they could be anything.
 impl<T> Apiv2Schema for Data<T> {}
@@ -248,13 +313,50 @@ impl Apiv2Operation for HttpResponse {
     }
 }
 
-impl_empty!(HttpRequest, HttpResponse, Bytes, Payload);
+impl_empty!(HttpRequest, HttpResponse);
+
+/// Raw request bodies are documented as binary data (`application/octet-stream` by default).
+macro_rules! impl_binary_body({ $($ty:ty),+ } => {
+    $(
+        impl Apiv2Schema for $ty {
+            fn raw_schema() -> DefaultSchemaRaw {
+                DefaultSchemaRaw {
+                    data_type: Some(DataType::String),
+                    format: Some(DataTypeFormat::Binary),
+                    ..Default::default()
+                }
+            }
+        }
+
+        impl OperationModifier for $ty {
+            fn update_parameter(op: &mut DefaultOperationRaw) {
+                op.parameters.push(Either::Right(Parameter {
+                    description: None,
+                    in_: ParameterIn::Body,
+                    name: "body".into(),
+                    required: true,
+                    schema: Some(Self::raw_schema()),
+                    ..Default::default()
+                }));
+                op.consumes.get_or_insert_with(|| {
+                    std::iter::once(MediaRange("application/octet-stream".parse().unwrap()))
+                        .collect()
+                });
+            }
+
+            fn update_definitions(_map: &mut BTreeMap<String, DefaultSchemaRaw>) {}
+        }
+    )+
+});
+
+impl_binary_body!(Bytes, Payload);
 
 #[cfg(not(feature = "nightly"))]
 mod manual_impl {
     use super::OperationModifier;
 
     impl<'a> OperationModifier for &'a str {}
+    impl<'a> OperationModifier for std::borrow::Cow<'a, str> {}
     impl<'a, T: OperationModifier> OperationModifier for &'a [T] {}
 
     macro_rules! impl_simple({ $ty:ty } => {
@@ -263,6 +365,7 @@ mod manual_impl {
 
     impl_simple!(char);
     impl_simple!(String);
+    impl_simple!(Box<str>);
     impl_simple!(bool);
     impl_simple!(f32);
     impl_simple!(f64);
@@ -278,8 +381,27 @@ mod manual_impl {
     impl_simple!(u64);
     impl_simple!(u128);
     impl_simple!(usize);
+    impl_simple!(std::path::PathBuf);
+    impl_simple!(std::num::NonZeroU8);
+    impl_simple!(std::num::NonZeroU16);
+    impl_simple!(std::num::NonZeroU32);
+    impl_simple!(std::num::NonZeroU64);
+    impl_simple!(std::num::NonZeroU128);
+    impl_simple!(std::num::NonZeroUsize);
+    impl_simple!(std::num::NonZeroI8);
+    impl_simple!(std::num::NonZeroI16);
+    impl_simple!(std::num::NonZeroI32);
+    impl_simple!(std::num::NonZeroI64);
+    impl_simple!(std::num::NonZeroI128);
+    impl_simple!(std::num::NonZeroIsize);
+    impl_simple!(std::time::Duration);
+    impl_simple!(std::time::SystemTime);
     #[cfg(feature = "chrono")]
     impl_simple!(chrono::NaiveDateTime);
+    #[cfg(feature = "chrono")]
+    impl_simple!(chrono::NaiveDate);
+    #[cfg(feature = "chrono")]
+    impl_simple!(chrono::NaiveTime);
     #[cfg(feature = "rust_decimal")]
     impl_simple!(rust_decimal::Decimal);
     #[cfg(feature = "url")]
@@ -327,11 +449,7 @@ where
             in_: ParameterIn::Body,
             name: "body".into(),
             required: true,
-            schema: Some({
-                let mut def = T::schema_with_ref();
-                def.retain_ref();
-                def
-            }),
+            schema: Some(request_body_schema::<T>()),
             ..Default::default()
         }));
     }
@@ -407,6 +525,32 @@ impl OperationModifier for actix_multipart::Multipart {
     }
 }
 
+#[cfg(all(feature = "actix-multipart", feature = "actix4"))]
+impl<T: actix_multipart::form::MultipartCollect> Apiv2Schema
+    for actix_multipart::form::MultipartForm<T>
+{
+}
+
+/// The fields of multipart forms are documented as `multipart/form-data` parameters.
+#[cfg(all(feature = "actix-multipart", feature = "actix4"))]
+impl<T> OperationModifier for actix_multipart::form::MultipartForm<T>
+where
+    T: actix_multipart::form::MultipartCollect + Apiv2Schema,
+{
+    fn update_parameter(op: &mut DefaultOperationRaw) {
+        // Multipart forms don't default to urlencoded bodies like `Form` does.
+        let consumes = op.consumes.take();
+        Form::<T>::update_parameter(op);
+        op.consumes = consumes;
+        op.consumes
+            .get_or_insert_with(Default::default)
+            .insert(MediaRange("multipart/form-data".parse().unwrap()));
+    }
+
+    // Only the fields of the form are documented.
+    fn update_definitions(_map: &mut BTreeMap<String, DefaultSchemaRaw>) {}
+}
+
 #[cfg(feature = "actix-session")]
 impl OperationModifier for actix_session::Session {
     fn update_definitions(_map: &mut BTreeMap<String, DefaultSchemaRaw>) {}
@@ -422,7 +566,7 @@ impl OperationModifier for actix_files::NamedFile {
     fn update_definitions(_map: &mut BTreeMap<String, DefaultSchemaRaw>) {}
 }
 
-macro_rules! impl_param_extractor ({ $ty:ty => $container:ident } => {
+macro_rules! impl_param_extractor ({ $ty:ty => $container:ident $(, consumes = $media:literal)? } => {
     #[cfg(feature = "nightly")]
     impl<T> Apiv2Schema for $ty {
         default fn name() -> Option<String> {
@@ -439,7 +583,7 @@ macro_rules! impl_param_extractor ({ $ty:ty => $container:ident } => {
 
     impl<T: Apiv2Schema> OperationModifier for $ty {
         fn update_parameter(op: &mut DefaultOperationRaw) {
-            let def = T::raw_schema();
+            let def = flatten_all_of(T::raw_schema(), &T::all_definitions());
             // If there aren't any properties and if it's a path parameter,
             // then add a parameter whose name will be overridden later.
             if def.properties.is_empty() && ParameterIn::$container == ParameterIn::Path {
@@ -450,24 +594,52 @@ macro_rules! impl_param_extractor ({ $ty:ty => $container:ident } => {
                     data_type: def.data_type,
                     format: def.format,
                     enum_: def.enum_,
+                    minimum: def.minimum,
                     description: def.description,
+                    example: def.example,
                     ..Default::default()
                 }));
             }
+            // An optional struct (e.g. `Query<Option<T>>`) makes all of its fields optional,
+            // except for the path parameters, which are always required.
+            let optional = !T::required() && ParameterIn::$container != ParameterIn::Path;
             for (k, v) in def.properties {
+                // Objects serialized as `deepObject` are described by their schema instead.
+                let object_schema = match v.style {
+                    Some(ParameterStyle::DeepObject) => Some((*v).clone()),
+                    _ => None,
+                };
                 op.parameters.push(Either::Right(Parameter {
                     in_: ParameterIn::$container,
-                    required: def.required.contains(&k),
+                    required: !optional && def.required.contains(&k),
                     data_type: v.data_type,
                     format: v.format,
                     enum_: v.enum_,
                     description: v.description,
+                    maximum: v.maximum,
+                    minimum: v.minimum,
+                    max_length: v.max_length,
+                    min_length: v.min_length,
+                    pattern: v.pattern,
+                    unique_items: v.unique_items,
+                    example: v.example,
+                    deprecated: v.deprecated,
+                    // Only query parameters may be sent empty.
+                    allow_empty_value: v.allow_empty_value
+                        && ParameterIn::$container == ParameterIn::Query,
                     collection_format: None, // this defaults to csv
                     items: v.items.as_deref().map(map_schema_to_items),
+                    style: v.style,
+                    object_schema,
                     name: k,
                     ..Default::default()
                 }));
             }
+            $(
+                op.consumes.get_or_insert_with(|| {
+                    std::iter::once(MediaRange($media.parse().unwrap())).collect()
+                });
+            )?
         }
 
         // These don't require updating definitions, as we use them only
@@ -476,6 +648,40 @@ macro_rules! impl_param_extractor ({ $ty:ty => $container:ident } => {
     }
 });
 
+/// Merges the properties of `allOf` members into the schema, so that
+/// flattened structs end up as individual parameters. Referenced members
+/// are resolved through the given definitions.
+fn flatten_all_of(
+    mut schema: DefaultSchemaRaw,
+    definitions: &BTreeMap<String, DefaultSchemaRaw>,
+) -> DefaultSchemaRaw {
+    for member in std::mem::take(&mut schema.all_of) {
+        let member = match member.reference.as_deref() {
+            Some(reference) => {
+                let name = reference
+                    .trim_start_matches("#/definitions/")
+                    .replace("%3C", "<")
+                    .replace("%3E", ">");
+                match definitions.get(&name) {
+                    // The definition is looked up without itself, so that
+                    // cyclic references don't recurse forever.
+                    Some(def) => {
+                        let mut definitions = definitions.clone();
+                        definitions.remove(&name);
+                        flatten_all_of(def.clone(), &definitions)
+                    }
+                    None => continue,
+                }
+            }
+            None => flatten_all_of(*member, definitions),
+        };
+        schema.properties.extend(member.properties);
+        schema.required.extend(member.required);
+    }
+
+    schema
+}
+
 fn map_schema_to_items(schema: &DefaultSchemaRaw) -> Items {
     Items {
         data_type: schema.data_type,
@@ -504,7 +710,7 @@ impl<T: Apiv2Schema> Apiv2Schema for Form<T> {
 
 impl_param_extractor!(Path<T> => Path);
 impl_param_extractor!(Query<T> => Query);
-impl_param_extractor!(Form<T> => FormData);
+impl_param_extractor!(Form<T> => FormData, consumes = "application/x-www-form-urlencoded");
 #[cfg(feature = "serde_qs")]
 impl_param_extractor!(QsQuery<T> => Query);
 #[cfg(any(feature = "actix4-validator", feature = "actix3-validator"))]
@@ -554,6 +760,7 @@ macro_rules! impl_path_tuple ({ $($ty:ident),+ } => {
                         data_type: def.data_type,
                         format: def.format,
                         enum_: def.enum_,
+                        minimum: def.minimum,
                         description: def.description,
                         ..Default::default()
                     }));
@@ -708,6 +915,28 @@ where
     }
 }
 
+/// Returns the schema of a request body. Read-only fields are only sent by the server,
+/// so if any of them are required, the schema is inlined without requiring them.
+fn request_body_schema<T>() -> DefaultSchemaRaw
+where
+    T: Apiv2Schema,
+{
+    let mut def = T::schema_with_ref();
+    let read_only = def
+        .properties
+        .iter()
+        .filter(|(name, s)| s.read_only && def.required.contains(*name))
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    if def.reference.is_some() && !read_only.is_empty() {
+        def.reference = None;
+        def.required.retain(|name| !read_only.contains(name));
+    }
+
+    def.retain_ref();
+    def
+}
+
 /// Given the schema type, recursively update the map of definitions.
 fn update_definitions_from_schema_type<T>(map: &mut BTreeMap<String, DefaultSchemaRaw>)
 where
@@ -913,3 +1142,86 @@ impl OperationModifier for NoContent {
         );
     }
 }
+
+/// Problem details of an error response ([RFC 7807](https://www.rfc-editor.org/rfc/rfc7807)),
+/// usually served as `application/problem+json`. Its schema can be used for error responses,
+/// e.g. `#[api_v2_errors(code = 400, schema = "ProblemDetails")]`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ProblemDetails {
+    /// URI reference identifying the problem type (`about:blank` if absent).
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    /// Short, human-readable summary of the problem type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// HTTP status code of this occurrence of the problem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Human-readable explanation specific to this occurrence of the problem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// URI reference identifying this occurrence of the problem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+}
+
+impl Apiv2Schema for ProblemDetails {
+    fn name() -> Option<String> {
+        Some("ProblemDetails".into())
+    }
+
+    fn description() -> &'static str {
+        "Problem details of an error response (RFC 7807)."
+    }
+
+    fn raw_schema() -> DefaultSchemaRaw {
+        let property = |data_type, description: &str| {
+            Box::new(DefaultSchemaRaw {
+                data_type: Some(data_type),
+                description: Some(description.into()),
+                ..Default::default()
+            })
+        };
+
+        let mut schema = DefaultSchemaRaw {
+            name: Self::name(),
+            data_type: Some(DataType::Object),
+            description: Some(Self::description().into()),
+            ..Default::default()
+        };
+        for (name, data_type, description) in [
+            (
+                "type",
+                DataType::String,
+                "URI reference identifying the problem type.",
+            ),
+            (
+                "title",
+                DataType::String,
+                "Short, human-readable summary of the problem type.",
+            ),
+            (
+                "status",
+                DataType::Integer,
+                "HTTP status code of this occurrence of the problem.",
+            ),
+            (
+                "detail",
+                DataType::String,
+                "Human-readable explanation specific to this occurrence of the problem.",
+            ),
+            (
+                "instance",
+                DataType::String,
+                "URI reference identifying this occurrence of the problem.",
+            ),
+        ] {
+            schema
+                .properties
+                .insert(name.into(), property(data_type, description));
+        }
+        schema
+    }
+}
+
+impl OperationModifier for ProblemDetails {}
@@ -211,7 +211,7 @@ impl<'de> Deserialize<'de> for Coders {
 /// Method used by openapiv3 crate.
 /// Works when deserializing but one could still add keys that don't start with "x-".
 /// todo: add own extensions map type that enforces "x-".
-pub(crate) fn deserialize_extensions<'de, D>(
+pub fn deserialize_extensions<'de, D>(
     deserializer: D,
 ) -> Result<BTreeMap<String, serde_json::Value>, D::Error>
 where
@@ -11,7 +11,8 @@ pub mod schema;
 
 #[cfg(feature = "actix-base")]
 pub use self::actix::{
-    AcceptedJson, CreatedJson, NoContent, OperationModifier, ResponderWrapper, ResponseWrapper,
+    AcceptedJson, CreatedJson, NoContent, OperationModifier, ProblemDetails, ResponderWrapper,
+    ResponseWrapper,
 };
 
 #[cfg(feature = "actix4")]
@@ -60,6 +61,9 @@ impl<S: Schema + Default> ResolvableApi<S> {
             responses: resolver.resp,
             spec_format: self.spec_format,
             external_docs: self.external_docs,
+            servers: self.servers,
+            #[cfg(feature = "oas31")]
+            webhooks: self.webhooks,
             security: self.security,
             security_definitions: self.security_definitions,
             tags: self.tags,
@@ -80,6 +80,9 @@ pub enum DataTypeFormat {
     Ip,
     IpV4,
     IpV6,
+    Decimal,
+    Int128,
+    Email,
     #[serde(other)]
     Other,
 }
@@ -101,6 +104,9 @@ impl ToString for DataTypeFormat {
             DataTypeFormat::Ip => "ip",
             DataTypeFormat::IpV4 => "ipv4",
             DataTypeFormat::IpV6 => "ipv6",
+            DataTypeFormat::Decimal => "decimal",
+            DataTypeFormat::Int128 => "int128",
+            DataTypeFormat::Email => "email",
             // would be nice if Other was Other(String)
             DataTypeFormat::Other => "other",
         }
@@ -125,6 +131,9 @@ impl From<DataTypeFormat> for DataType {
             DataTypeFormat::Ip => Self::String,
             DataTypeFormat::IpV4 => Self::String,
             DataTypeFormat::IpV6 => Self::String,
+            DataTypeFormat::Decimal => Self::Number,
+            DataTypeFormat::Int128 => Self::Integer,
+            DataTypeFormat::Email => Self::String,
             DataTypeFormat::Other => Self::Object,
         }
     }
@@ -202,6 +211,16 @@ pub struct Api<P, R, S> {
     pub tags: Vec<Tag>,
     #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
     pub external_docs: Option<ExternalDocs>,
+    /// Root-level servers. Swagger 2.0 has no equivalent for these, so they're
+    /// only emitted in the converted v3 spec (where they take precedence over
+    /// the servers derived from `host`, `basePath` and `schemes`).
+    #[serde(skip)]
+    pub servers: Vec<Server>,
+    /// Webhooks (OpenAPI 3.1) keyed by their names. These are only emitted
+    /// in the converted v3 spec.
+    #[cfg(feature = "oas31")]
+    #[serde(skip)]
+    pub webhooks: BTreeMap<String, DefaultPathItemRaw>,
     /// Extension for custom coders to be used for decoding API objects.
     ///
     /// An example for JSON would be:
@@ -285,8 +304,135 @@ impl<P, R, S> Api<P, R, S> {
     ) -> Cow<'_, str> {
         PATH_TEMPLATE_REGEX.replace_all(path, |c: &Captures| f(&c[1]))
     }
+
+    /// Sets the tag groups of this spec, which are emitted as the `x-tagGroups`
+    /// extension (used by Redoc for grouping tags in its navigation).
+    pub fn set_tag_groups<I>(&mut self, groups: I)
+    where
+        I: IntoIterator<Item = TagGroup>,
+    {
+        let groups = groups.into_iter().collect::<Vec<_>>();
+        if groups.is_empty() {
+            self.extensions.remove(TAG_GROUPS_EXTENSION);
+        } else {
+            self.extensions.insert(
+                TAG_GROUPS_EXTENSION.into(),
+                serde_json::to_value(groups).expect("serializing tag groups"),
+            );
+        }
+    }
+
+    /// Marks the operations having any of the deprecated tags as deprecated.
+    pub fn deprecate_tagged_operations(&mut self) {
+        let deprecated_tags = self
+            .tags
+            .iter()
+            .filter(|t| t.deprecated)
+            .map(|t| t.name.as_str())
+            .collect::<BTreeSet<_>>();
+        if deprecated_tags.is_empty() {
+            return;
+        }
+
+        for op in self.paths.values_mut().flat_map(|p| p.methods.values_mut()) {
+            if op.tags.iter().any(|t| deprecated_tags.contains(t.as_str())) {
+                op.deprecated = true;
+            }
+        }
+    }
+
+    /// Checks that the operation IDs are unique across all the operations of this spec.
+    pub fn check_operation_ids(&self) -> Result<(), ValidationError> {
+        let mut operations = BTreeMap::<_, Vec<_>>::new();
+        for (path, item) in &self.paths {
+            for (method, op) in &item.methods {
+                if let Some(id) = op.operation_id.as_ref() {
+                    operations.entry(id.clone()).or_default().push(format!(
+                        "{} {}",
+                        method.to_string().to_uppercase(),
+                        path
+                    ));
+                }
+            }
+        }
+
+        let duplicates = operations
+            .into_iter()
+            .filter(|(_, ops)| ops.len() > 1)
+            .collect::<Vec<_>>();
+        if duplicates.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError::DuplicateOperationIds(duplicates))
+        }
+    }
 }
 
+impl<S: Clone> Api<Parameter<S>, Response<S>, S> {
+    /// Uses the given schema for the error (`4xx`, `5xx` and `default`) responses
+    /// of all operations, which don't have a schema of their own.
+    pub fn fill_error_schemas(&mut self, schema: &S) {
+        let responses = self
+            .paths
+            .values_mut()
+            .flat_map(|p| p.methods.values_mut())
+            .flat_map(|op| op.responses.iter_mut());
+        for (code, response) in responses {
+            let is_error = code == "default" || matches!(code.parse::<u16>(), Ok(c) if c >= 400);
+            if let Either::Right(response) = response {
+                if is_error && response.schema.is_none() {
+                    response.schema = Some(schema.clone());
+                }
+            }
+        }
+    }
+
+    /// Replaces the inline parameters of all operations, which have the same name and
+    /// location as one of the shared `parameters` of the spec, with references to it.
+    ///
+    /// Only header, query and path parameters are referenced, since shared body and
+    /// form parameters have no counterpart in the v3 `components.parameters`.
+    pub fn reference_shared_parameters(&mut self) {
+        let shared = self
+            .parameters
+            .iter()
+            .filter(|(_, p)| {
+                matches!(
+                    p.in_,
+                    ParameterIn::Header | ParameterIn::Query | ParameterIn::Path
+                )
+            })
+            .collect::<Vec<_>>();
+        if shared.is_empty() {
+            return;
+        }
+
+        let parameters = self.paths.values_mut().flat_map(|p| {
+            p.methods
+                .values_mut()
+                .flat_map(|op| op.parameters.iter_mut())
+                .chain(p.parameters.iter_mut())
+        });
+        for param in parameters {
+            let key = match param {
+                Either::Right(p) => shared
+                    .iter()
+                    .find(|(_, s)| s.name == p.name && s.in_ == p.in_)
+                    .map(|(key, _)| key),
+                Either::Left(_) => None,
+            };
+            if let Some(key) = key {
+                *param = Either::Left(Reference {
+                    reference: format!("#/parameters/{}", key),
+                });
+            }
+        }
+    }
+}
+
+/// Name of the extension holding tag groups.
+const TAG_GROUPS_EXTENSION: &str = "x-tagGroups";
+
 use crate as paperclip; // hack for proc macro
 
 /// Default schema if your schema doesn't have any custom fields.
@@ -305,6 +451,8 @@ pub struct Info {
     pub title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(rename = "termsOfService", skip_serializing_if = "Option::is_none")]
+    pub terms_of_service: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contact: Option<Contact>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -318,6 +466,41 @@ pub struct Info {
     pub extensions: BTreeMap<String, serde_json::Value>,
 }
 
+impl Info {
+    /// Creates the info block with the (required) title and API version.
+    pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+        Info {
+            title: title.into(),
+            version: version.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the description of the API.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the URL to the terms of service of the API.
+    pub fn terms_of_service(mut self, url: impl Into<String>) -> Self {
+        self.terms_of_service = Some(url.into());
+        self
+    }
+
+    /// Sets the contact information for the API.
+    pub fn contact(mut self, contact: Contact) -> Self {
+        self.contact = Some(contact);
+        self
+    }
+
+    /// Sets the license information for the API.
+    pub fn license(mut self, license: License) -> Self {
+        self.license = Some(license);
+        self
+    }
+}
+
 /// Contact object.
 ///
 /// <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#contactObject>
@@ -331,6 +514,28 @@ pub struct Contact {
     pub email: Option<String>,
 }
 
+impl Contact {
+    /// Creates contact information with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Contact {
+            name: Some(name.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the URL pointing to the contact information.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Sets the email address of the contact.
+    pub fn email(mut self, email: impl Into<String>) -> Self {
+        self.email = Some(email.into());
+        self
+    }
+}
+
 /// License object.
 ///
 /// <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#licenseObject>
@@ -342,6 +547,22 @@ pub struct License {
     pub url: Option<String>,
 }
 
+impl License {
+    /// Creates license information with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        License {
+            name: Some(name.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the URL to the license used for the API.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+}
+
 /// Security Scheme object.
 ///
 /// <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#security-scheme-object>
@@ -407,6 +628,105 @@ pub struct Tag {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "externalDocs")]
     pub external_docs: Option<ExternalDocs>,
+    /// Whether all the operations under this tag are deprecated. Tags don't
+    /// have a `deprecated` field, so this is emitted as the `x-tag-deprecated` extension.
+    #[serde(default, rename = "x-tag-deprecated", skip_serializing_if = "is_false")]
+    pub deprecated: bool,
+}
+
+/// Group of tags (`x-tagGroups` extension).
+///
+/// <https://redocly.com/docs/api-reference-docs/specification-extensions/x-tag-groups/>
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TagGroup {
+    pub name: String,
+    pub tags: Vec<String>,
+}
+
+impl TagGroup {
+    /// Creates a group with the given name and tag names.
+    pub fn new<I, T>(name: impl Into<String>, tags: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        TagGroup {
+            name: name.into(),
+            tags: tags.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Server object (OpenAPI v3 only).
+///
+/// <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.0.3.md#server-object>
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Server {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, ServerVariable>,
+}
+
+impl Server {
+    /// Creates a server for the given (possibly templated) URL.
+    pub fn new(url: impl Into<String>) -> Self {
+        Server {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the description of this server.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Adds a variable for substitution in the server's URL template.
+    pub fn variable(mut self, name: impl Into<String>, variable: ServerVariable) -> Self {
+        self.variables.insert(name.into(), variable);
+        self
+    }
+}
+
+/// Server variable object (OpenAPI v3 only).
+///
+/// <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.0.3.md#server-variable-object>
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ServerVariable {
+    pub default: String,
+    #[serde(default, rename = "enum", skip_serializing_if = "Vec::is_empty")]
+    pub enum_: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl ServerVariable {
+    /// Creates a variable with the given default value.
+    pub fn new(default: impl Into<String>) -> Self {
+        ServerVariable {
+            default: default.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Restricts the variable to the given set of values.
+    pub fn enum_values<I, T>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.enum_ = values.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the description of this variable.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
 }
 
 /// External Documentation object.
@@ -419,6 +739,23 @@ pub struct ExternalDocs {
     pub url: String,
 }
 
+/// XML object.
+///
+/// <https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#xml-object>
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Xml {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub attribute: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub wrapped: bool,
+}
+
 /// Path item that can be traversed and resolved for codegen.
 pub type ResolvablePathItem<S> = PathItem<ResolvableParameter<S>, ResolvableResponse<S>>;
 
@@ -539,6 +876,21 @@ pub struct Parameter<S> {
     pub multiple_of: Option<f32>,
     #[serde(default, rename = "enum", skip_serializing_if = "Vec::is_empty")]
     pub enum_: Vec<serde_json::Value>,
+    /// Example value of this parameter. Non-body parameters don't have an `example`
+    /// field in v2, so this is emitted as the `x-example` extension.
+    #[serde(rename = "x-example", skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+    /// Whether this parameter is deprecated. Parameters don't have a `deprecated`
+    /// field in v2, so this is emitted as the `x-deprecated` extension.
+    #[serde(default, rename = "x-deprecated", skip_serializing_if = "is_false")]
+    pub deprecated: bool,
+    /// Serialization style of this (query) parameter. This is only emitted in OpenAPI v3.
+    #[serde(skip)]
+    pub style: Option<ParameterStyle>,
+    /// Schema of a `deepObject` (query) parameter. Non-body parameters don't have a schema
+    /// in v2, so this is only emitted in OpenAPI v3.
+    #[serde(skip, default = "Option::default")]
+    pub object_schema: Option<S>,
 }
 
 /// Items object.
@@ -682,6 +1034,17 @@ pub enum CollectionFormat {
     Multi,
 }
 
+/// Serialization styles of query parameters. OpenAPI v2 only has collection
+/// formats, so these are only emitted in OpenAPI v3.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ParameterStyle {
+    Form,
+    SpaceDelimited,
+    PipeDelimited,
+    DeepObject,
+}
+
 /// Operation that can be traversed and resolved for codegen.
 pub type ResolvableOperation<S> = Operation<ResolvableParameter<S>, ResolvableResponse<S>>;
 
@@ -719,9 +1082,89 @@ pub struct Operation<P, R> {
     pub deprecated: bool,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
+    /// Inline extensions to this object.
+    #[serde(
+        flatten,
+        skip_serializing_if = "BTreeMap::is_empty",
+        deserialize_with = "crate::v2::extensions::deserialize_extensions"
+    )]
+    pub extensions: BTreeMap<String, serde_json::Value>,
+    /// Whether the request body is kept for methods which don't define its semantics
+    /// (i.e., `GET`, `DELETE` and `HEAD`).
+    #[serde(skip)]
+    pub allow_get_body: bool,
+    /// Out-of-band requests made by the API (mapped by name and then by the URL
+    /// expression). Swagger 2.0 doesn't support these, so they're only emitted in
+    /// the converted v3 spec.
+    #[serde(skip)]
+    pub callbacks: BTreeMap<String, BTreeMap<String, DefaultPathItemRaw>>,
+}
+
+impl<P, R> Operation<P, R> {
+    /// Adds a code sample for this operation to the `x-codeSamples` extension
+    /// (used by Redoc for showing request samples).
+    pub fn add_code_sample(&mut self, lang: &str, source: &str) {
+        let sample = serde_json::json!({
+            "lang": lang,
+            "source": source,
+        });
+        match self
+            .extensions
+            .entry(CODE_SAMPLES_EXTENSION.into())
+            .or_insert_with(|| serde_json::Value::Array(vec![]))
+        {
+            serde_json::Value::Array(samples) => samples.push(sample),
+            other => *other = serde_json::Value::Array(vec![sample]),
+        }
+    }
+
+    /// Adds the given operation to the named callback, for requests made to the
+    /// given URL expression (e.g., `{$request.body#/callbackUrl}`).
+    pub fn add_callback(
+        &mut self,
+        name: &str,
+        expression: &str,
+        method: HttpMethod,
+        operation: DefaultOperationRaw,
+    ) {
+        self.callbacks
+            .entry(name.into())
+            .or_default()
+            .entry(expression.into())
+            .or_default()
+            .methods
+            .insert(method, operation);
+    }
 }
 
+/// Name of the extension holding code samples of an operation.
+const CODE_SAMPLES_EXTENSION: &str = "x-codeSamples";
+
+/// Patterns of path segments capturing the remaining path.
+const TAIL_PATTERNS: &[&str] = &[".*", ".+"];
+
 impl<S> Operation<Parameter<S>, Response<S>> {
+    /// Removes the request body of this operation if it's used with a method
+    /// which doesn't define the semantics of a body (`GET`, `DELETE` or `HEAD`),
+    /// unless it's been explicitly allowed.
+    pub fn remove_body_for_method(&mut self, method: HttpMethod) {
+        if self.allow_get_body
+            || !matches!(
+                method,
+                HttpMethod::Get | HttpMethod::Delete | HttpMethod::Head
+            )
+        {
+            return;
+        }
+
+        self.parameters.retain(|p| {
+            !matches!(
+                p.right().map(|p| p.in_),
+                Some(ParameterIn::Body | ParameterIn::FormData)
+            )
+        });
+    }
+
     /// Overwrites the names of parameters in this operation using the
     /// given path template.
     pub fn set_parameter_names_from_path_template(&mut self, path: &str) {
@@ -749,7 +1192,11 @@ impl<S> Operation<Parameter<S>, Response<S>> {
             if let Some(n) = names.pop() {
                 if let Some((name, pattern)) = n.split_once(':') {
                     p.name = name.to_string();
-                    p.pattern = Some(pattern.to_string());
+                    // Tail segments (e.g., `{tail:.*}`) match the rest of the path
+                    // including slashes, so their pattern isn't useful to clients.
+                    if !TAIL_PATTERNS.contains(&pattern) {
+                        p.pattern = Some(pattern.to_string());
+                    }
                 } else {
                     p.name = n;
                 }
@@ -796,6 +1243,39 @@ pub struct Response<S> {
     pub schema: Option<S>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub headers: BTreeMap<String, Header>,
+    /// Named examples of the response body. OpenAPI v2 only supports examples
+    /// keyed by media type, so these are only emitted in OpenAPI v3.
+    #[serde(skip)]
+    pub examples: BTreeMap<String, serde_json::Value>,
+    /// Links to operations which can follow this response. These are only
+    /// emitted in OpenAPI v3.
+    #[serde(skip)]
+    pub links: BTreeMap<String, Link>,
+    /// Schemas of the response body for the media types producing different
+    /// schemas (content negotiation). OpenAPI v2 has a single schema for all
+    /// media types, so these are only emitted in OpenAPI v3.
+    #[serde(skip, default = "BTreeMap::new")]
+    pub content_schemas: BTreeMap<String, S>,
+    /// Media types of this response, overriding the ones produced by the operation
+    /// (e.g., `application/problem+json` for errors). OpenAPI v2 only has media types
+    /// for the whole operation, so these are only emitted in OpenAPI v3.
+    #[serde(skip)]
+    pub produces: Option<BTreeSet<MediaRange>>,
+}
+
+/// Link object (OpenAPI v3 only).
+///
+/// <https://github.com/OAI/OpenAPI-Specification/blob/main/versions/3.0.3.md#link-object>
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Link {
+    pub operation_id: String,
+    /// Parameters of the linked operation mapped to their values
+    /// (constants or runtime expressions, e.g. `$response.body#/id`).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 /// Header object.
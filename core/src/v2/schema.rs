@@ -5,7 +5,14 @@ use super::models::{
     SecurityScheme,
 };
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+};
+
+// Used by the schema struct (`#[api_v2_schema_struct]`) for its extensions.
+#[doc(hidden)]
+pub use super::extensions::deserialize_extensions;
 
 /// Interface for the [`Schema`](https://github.com/OAI/OpenAPI-Specification/blob/master/versions/2.0.md#schemaObject) object.
 ///
@@ -127,6 +134,11 @@ pub trait TypedData {
     fn format() -> Option<DataTypeFormat> {
         None
     }
+
+    /// The optional minimum value of this implementor.
+    fn minimum() -> Option<f32> {
+        None
+    }
 }
 
 macro_rules! impl_type_simple {
@@ -158,6 +170,12 @@ impl<'a> TypedData for &'a str {
     }
 }
 
+impl<'a> TypedData for std::borrow::Cow<'a, str> {
+    fn data_type() -> DataType {
+        DataType::String
+    }
+}
+
 impl<'a, T: TypedData> TypedData for &'a T {
     fn data_type() -> DataType {
         T::data_type()
@@ -170,6 +188,7 @@ impl<'a, T: TypedData> TypedData for &'a T {
 
 impl_type_simple!(char, DataType::String);
 impl_type_simple!(String, DataType::String);
+impl_type_simple!(Box<str>, DataType::String);
 impl_type_simple!(PathBuf, DataType::String);
 impl_type_simple!(bool, DataType::Boolean);
 impl_type_simple!(f32, DataType::Number, DataTypeFormat::Float);
@@ -193,6 +212,18 @@ impl_type_simple!(
     DataType::File,
     DataTypeFormat::Binary
 );
+#[cfg(all(feature = "actix-multipart", feature = "actix4"))]
+impl_type_simple!(
+    actix_multipart::form::tempfile::TempFile,
+    DataType::File,
+    DataTypeFormat::Binary
+);
+#[cfg(all(feature = "actix-multipart", feature = "actix4"))]
+impl_type_simple!(
+    actix_multipart::form::bytes::Bytes,
+    DataType::File,
+    DataTypeFormat::Binary
+);
 #[cfg(feature = "actix-session")]
 impl_type_simple!(actix_session::Session);
 #[cfg(feature = "actix-identity")]
@@ -249,6 +280,51 @@ impl<T: chrono::offset::TimeZone> TypedData for chrono::Date<T> {
     }
 }
 
+// Unsigned non-zero integers are positive, but signed ones can also be negative,
+// so only the former have a lower bound.
+macro_rules! impl_type_non_zero {
+    ($ty:ty, $df:expr) => {
+        impl_type_non_zero!($ty, $df, None);
+    };
+    ($ty:ty, $df:expr, $min:expr) => {
+        impl TypedData for $ty {
+            fn data_type() -> DataType {
+                DataType::Integer
+            }
+            fn format() -> Option<DataTypeFormat> {
+                Some($df)
+            }
+            fn minimum() -> Option<f32> {
+                $min
+            }
+        }
+    };
+}
+
+impl_type_non_zero!(std::num::NonZeroU8, DataTypeFormat::Int32, Some(1.0));
+impl_type_non_zero!(std::num::NonZeroU16, DataTypeFormat::Int32, Some(1.0));
+impl_type_non_zero!(std::num::NonZeroU32, DataTypeFormat::Int32, Some(1.0));
+impl_type_non_zero!(std::num::NonZeroU64, DataTypeFormat::Int64, Some(1.0));
+impl_type_non_zero!(std::num::NonZeroU128, DataTypeFormat::Int64, Some(1.0));
+impl_type_non_zero!(std::num::NonZeroUsize, DataTypeFormat::Int64, Some(1.0));
+impl_type_non_zero!(std::num::NonZeroI8, DataTypeFormat::Int32);
+impl_type_non_zero!(std::num::NonZeroI16, DataTypeFormat::Int32);
+impl_type_non_zero!(std::num::NonZeroI32, DataTypeFormat::Int32);
+impl_type_non_zero!(std::num::NonZeroI64, DataTypeFormat::Int64);
+impl_type_non_zero!(std::num::NonZeroI128, DataTypeFormat::Int64);
+impl_type_non_zero!(std::num::NonZeroIsize, DataTypeFormat::Int64);
+
+// Durations are usually sent as a number of seconds, and timestamps as RFC 3339 strings.
+impl_type_simple!(
+    std::time::Duration,
+    DataType::Integer,
+    DataTypeFormat::Int64
+);
+impl_type_simple!(
+    std::time::SystemTime,
+    DataType::String,
+    DataTypeFormat::DateTime
+);
 impl_type_simple!(std::net::IpAddr, DataType::String, DataTypeFormat::Ip);
 impl_type_simple!(std::net::Ipv4Addr, DataType::String, DataTypeFormat::IpV4);
 impl_type_simple!(std::net::Ipv6Addr, DataType::String, DataTypeFormat::IpV6);
@@ -301,11 +377,9 @@ pub trait Apiv2Schema {
     fn schema_with_ref() -> DefaultSchemaRaw {
         let mut def = Self::raw_schema();
         if let Some(n) = Self::name() {
-            def.reference =
-                Some(String::from("#/definitions/") + &n.replace('<', "%3C").replace('>', "%3E"));
+            def.reference = Some(definition_reference(&n));
         } else if let Some(n) = def.name.as_ref() {
-            def.reference =
-                Some(String::from("#/definitions/") + &n.replace('<', "%3C").replace('>', "%3E"));
+            def.reference = Some(definition_reference(n));
         }
         if !Self::description().is_empty() {
             def.description = Some(Self::description().to_owned());
@@ -314,6 +388,14 @@ pub trait Apiv2Schema {
         def
     }
 
+    /// Returns the definitions of this object and all the named objects nested in it
+    /// (e.g., for writing standalone schema files or for asserting the schemas in tests).
+    fn all_definitions() -> BTreeMap<String, DefaultSchemaRaw> {
+        let mut map = BTreeMap::new();
+        collect_definitions(&Self::schema_with_ref(), &mut map);
+        map
+    }
+
     /// Returns the security scheme for this object.
     fn security_scheme() -> Option<SecurityScheme> {
         None
@@ -324,6 +406,71 @@ pub trait Apiv2Schema {
     }
 }
 
+/// Returns the `$ref` path to the definition with the given name.
+fn definition_reference(name: &str) -> String {
+    String::from("#/definitions/") + &name.replace('<', "%3C").replace('>', "%3E")
+}
+
+/// Recursively adds the given schema and its nested schemas (if they're named) to the map.
+fn collect_definitions(schema: &DefaultSchemaRaw, map: &mut BTreeMap<String, DefaultSchemaRaw>) {
+    let nested = schema
+        .properties
+        .values()
+        .chain(schema.items.iter())
+        .chain(schema.extra_props.as_ref().and_then(|s| s.right()))
+        .chain(schema.any_of.iter())
+        .chain(schema.one_of.iter())
+        .chain(schema.all_of.iter());
+    for s in nested {
+        collect_definitions(s, map);
+    }
+
+    if let Some(name) = schema.name.as_ref() {
+        if !map.contains_key(name) {
+            let mut def = schema.clone();
+            def.name = None;
+            def.remove_refs();
+            map.insert(name.clone(), def);
+        }
+    }
+}
+
+thread_local! {
+    /// Names of the schemas currently being built (in this thread).
+    static SCHEMAS_IN_PROGRESS: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
+}
+
+/// Builds the raw schema for the named type using the given function (used by
+/// the derived `Apiv2Schema::raw_schema`).
+///
+/// Nested schemas are usually inlined, which doesn't work for recursive types.
+/// If the type is encountered again while its schema is still being built,
+/// then a `$ref` to its definition is returned instead.
+#[doc(hidden)]
+pub fn raw_schema_guarded<F>(name: &str, f: F) -> DefaultSchemaRaw
+where
+    F: FnOnce() -> DefaultSchemaRaw,
+{
+    struct Guard<'a>(&'a str);
+
+    impl Drop for Guard<'_> {
+        fn drop(&mut self) {
+            SCHEMAS_IN_PROGRESS.with(|s| s.borrow_mut().remove(self.0));
+        }
+    }
+
+    if !SCHEMAS_IN_PROGRESS.with(|s| s.borrow_mut().insert(name.into())) {
+        return DefaultSchemaRaw {
+            reference: Some(definition_reference(name)),
+            cyclic: true,
+            ..Default::default()
+        };
+    }
+
+    let _guard = Guard(name);
+    f()
+}
+
 impl Apiv2Schema for () {}
 impl Apiv2Schema for serde_json::Value {}
 impl Apiv2Schema for serde_yaml::Value {}
@@ -333,6 +480,7 @@ impl<T: TypedData> Apiv2Schema for T {
         DefaultSchemaRaw {
             data_type: Some(T::data_type()),
             format: T::format(),
+            minimum: T::minimum(),
             ..Default::default()
         }
     }
@@ -383,6 +531,24 @@ impl<T: Apiv2Schema> Apiv2Schema for Option<T> {
     }
 }
 
+#[cfg(all(feature = "actix-multipart", feature = "actix4"))]
+impl<T> Apiv2Schema for actix_multipart::form::text::Text<T>
+where
+    T: serde::de::DeserializeOwned + Apiv2Schema,
+{
+    fn name() -> Option<String> {
+        T::name()
+    }
+
+    fn required() -> bool {
+        T::required()
+    }
+
+    fn raw_schema() -> DefaultSchemaRaw {
+        T::raw_schema()
+    }
+}
+
 #[cfg(feature = "nightly")]
 impl<T, E> Apiv2Schema for Result<T, E> {
     default fn name() -> Option<String> {
@@ -446,11 +612,15 @@ impl<'a, T: Apiv2Schema> Apiv2Schema for &'a [T] {
 
 macro_rules! impl_schema_array {
     ($ty:ty) => {
+        impl_schema_array!($ty, false);
+    };
+    ($ty:ty, $unique:expr) => {
         impl<T: Apiv2Schema> Apiv2Schema for $ty {
             fn raw_schema() -> DefaultSchemaRaw {
                 DefaultSchemaRaw {
                     data_type: Some(DataType::Array),
                     items: Some(T::schema_with_ref().into()),
+                    unique_items: $unique,
                     ..Default::default()
                 }
             }
@@ -476,10 +646,11 @@ use crate::v2::models::Parameter;
 use std::{collections::*, path::PathBuf};
 
 impl_schema_array!(Vec<T>);
-impl_schema_array!(HashSet<T>);
+// Sets can't have duplicate items.
+impl_schema_array!(HashSet<T>, true);
 impl_schema_array!(LinkedList<T>);
 impl_schema_array!(VecDeque<T>);
-impl_schema_array!(BTreeSet<T>);
+impl_schema_array!(BTreeSet<T>, true);
 impl_schema_array!(BinaryHeap<T>);
 impl_schema_array!([T; 0]);
 impl_schema_array!([T; 1]);
@@ -518,6 +689,55 @@ impl_schema_array!([T; 32]);
 impl_schema_map!(HashMap<K, V>);
 impl_schema_map!(BTreeMap<K, V>);
 
+/// Schema of a tuple (used by the derived `Apiv2Schema` for tuple fields).
+///
+/// Tuples are arrays of fixed length. If the elements have different schemas,
+/// then the items can be any of them.
+#[doc(hidden)]
+pub struct TupleSchema<T>(std::marker::PhantomData<T>);
+
+macro_rules! impl_schema_tuple {
+    ($len:expr => $($ty:ident),+) => {
+        impl<$($ty: Apiv2Schema),+> Apiv2Schema for TupleSchema<($($ty,)+)> {
+            fn raw_schema() -> DefaultSchemaRaw {
+                let mut items: Vec<DefaultSchemaRaw> = vec![];
+                $(
+                    let item = $ty::schema_with_ref();
+                    let value = serde_json::to_value(&item).ok();
+                    if !items.iter().any(|i| serde_json::to_value(i).ok() == value) {
+                        items.push(item);
+                    }
+                )+
+                let items = if items.len() == 1 {
+                    items.remove(0)
+                } else {
+                    DefaultSchemaRaw {
+                        any_of: items.into_iter().map(Box::new).collect(),
+                        ..Default::default()
+                    }
+                };
+
+                DefaultSchemaRaw {
+                    data_type: Some(DataType::Array),
+                    items: Some(items.into()),
+                    min_items: Some($len),
+                    max_items: Some($len),
+                    ..Default::default()
+                }
+            }
+        }
+    };
+}
+
+impl_schema_tuple!(1 => A);
+impl_schema_tuple!(2 => A, B);
+impl_schema_tuple!(3 => A, B, C);
+impl_schema_tuple!(4 => A, B, C, D);
+impl_schema_tuple!(5 => A, B, C, D, E);
+impl_schema_tuple!(6 => A, B, C, D, E, F);
+impl_schema_tuple!(7 => A, B, C, D, E, F, G);
+impl_schema_tuple!(8 => A, B, C, D, E, F, G, H);
+
 /// Represents a OpenAPI v2 operation convertible. This is auto-implemented by
 /// framework-specific macros:
 ///
@@ -548,6 +768,12 @@ pub trait Apiv2Errors {
     const ERROR_MAP: &'static [(u16, &'static str)] = &[];
     fn update_error_definitions(_op: &mut DefaultOperationRaw) {}
     fn update_definitions(_map: &mut BTreeMap<String, DefaultSchemaRaw>) {}
+
+    /// Returns the status code (and its description) declared for this error value, if any
+    /// (e.g., for enum variants marked with `#[api_v2_errors_variant]`).
+    fn error_code(&self) -> Option<(u16, &'static str)> {
+        None
+    }
 }
 
 impl Apiv2Errors for () {}
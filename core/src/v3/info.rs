@@ -5,7 +5,7 @@ impl From<v2::Info> for openapiv3::Info {
         openapiv3::Info {
             title: v2.title,
             description: v2.description,
-            terms_of_service: None,
+            terms_of_service: v2.terms_of_service,
             contact: v2.contact.map(|c| c.into()),
             license: v2.license.map(From::from),
             version: v2.version,
@@ -24,7 +24,129 @@ use parameter::non_body_parameter_to_v3_parameter;
 use reference::invalid_referenceor;
 use response::OperationEitherResponse;
 
+use std::collections::BTreeSet;
+
 /// Convert this crates openapi v2 (`DefaultApiRaw`) to `openapiv3::OpenAPI`
 pub fn openapiv2_to_v3(v2: v2::DefaultApiRaw) -> openapiv3::OpenAPI {
     openapiv3::OpenAPI::from(v2)
 }
+
+/// Removes the schemas in `components` which aren't (transitively) referenced by any path
+/// or by other (non-schema) components, e.g. when a shared map of definitions is used.
+pub fn prune_unused_definitions(spec: &mut openapiv3::OpenAPI) {
+    // Schemas are taken out, so that only the references from other components remain.
+    let mut schemas = match spec.components.as_mut() {
+        Some(c) => std::mem::take(&mut c.schemas),
+        None => return,
+    };
+
+    let mut pending = Vec::new();
+    collect_schema_refs(
+        &serde_json::to_value(&spec.paths).unwrap_or_default(),
+        &mut pending,
+    );
+    if let Some(components) = spec.components.as_ref() {
+        collect_schema_refs(
+            &serde_json::to_value(components).unwrap_or_default(),
+            &mut pending,
+        );
+    }
+    // Webhooks live in the extensions.
+    collect_schema_refs(
+        &serde_json::to_value(&spec.extensions).unwrap_or_default(),
+        &mut pending,
+    );
+
+    let mut reachable = BTreeSet::new();
+    while let Some(name) = pending.pop() {
+        if !reachable.insert(name.clone()) {
+            continue;
+        }
+
+        if let Some(schema) = schemas.get(&name) {
+            collect_schema_refs(
+                &serde_json::to_value(schema).unwrap_or_default(),
+                &mut pending,
+            );
+        }
+    }
+
+    schemas.retain(|name, _| reachable.contains(name));
+    if let Some(components) = spec.components.as_mut() {
+        components.schemas = schemas;
+    }
+}
+
+/// Collects the names of the schemas referenced (at any depth) in the given value.
+fn collect_schema_refs(value: &serde_json::Value, names: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    serde_json::Value::String(r) if key == "$ref" => {
+                        if let Some(name) = r.strip_prefix("#/components/schemas/") {
+                            names.push(name.replace("%3C", "<").replace("%3E", ">"));
+                        }
+                    }
+                    _ => collect_schema_refs(value, names),
+                }
+            }
+        }
+        serde_json::Value::Array(values) => {
+            values.iter().for_each(|v| collect_schema_refs(v, names))
+        }
+        _ => (),
+    }
+}
+
+/// Sorts the paths, the components and the properties of component schemas alphabetically,
+/// so that the serialized spec is byte-for-byte stable (e.g., for diffing it in CI).
+pub fn canonicalize(spec: &mut openapiv3::OpenAPI) {
+    spec.paths.paths.sort_keys();
+    if let Some(components) = spec.components.as_mut() {
+        components.schemas.sort_keys();
+        components.responses.sort_keys();
+        components.parameters.sort_keys();
+        components.examples.sort_keys();
+        components.request_bodies.sort_keys();
+        components.headers.sort_keys();
+        components.security_schemes.sort_keys();
+        for schema in components.schemas.values_mut() {
+            if let openapiv3::ReferenceOr::Item(schema) = schema {
+                sort_schema_properties(schema);
+            }
+        }
+    }
+}
+
+/// Recursively sorts the properties of the given schema.
+fn sort_schema_properties(schema: &mut openapiv3::Schema) {
+    let properties = match &mut schema.schema_kind {
+        openapiv3::SchemaKind::Type(openapiv3::Type::Object(object)) => &mut object.properties,
+        openapiv3::SchemaKind::Any(any) => &mut any.properties,
+        openapiv3::SchemaKind::Type(openapiv3::Type::Array(array)) => {
+            if let Some(openapiv3::ReferenceOr::Item(items)) = array.items.as_mut() {
+                sort_schema_properties(items);
+            }
+            return;
+        }
+        openapiv3::SchemaKind::OneOf { one_of: schemas }
+        | openapiv3::SchemaKind::AllOf { all_of: schemas }
+        | openapiv3::SchemaKind::AnyOf { any_of: schemas } => {
+            for schema in schemas {
+                if let openapiv3::ReferenceOr::Item(schema) = schema {
+                    sort_schema_properties(schema);
+                }
+            }
+            return;
+        }
+        _ => return,
+    };
+
+    properties.sort_keys();
+    for property in properties.values_mut() {
+        if let openapiv3::ReferenceOr::Item(property) = property {
+            sort_schema_properties(property);
+        }
+    }
+}
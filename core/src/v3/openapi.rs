@@ -1,4 +1,4 @@
-use super::v2;
+use super::{v2, Either};
 
 impl From<v2::DefaultApiRaw> for openapiv3::OpenAPI {
     fn from(v2: v2::DefaultApiRaw) -> Self {
@@ -6,7 +6,11 @@ impl From<v2::DefaultApiRaw> for openapiv3::OpenAPI {
             openapi: "3.0.0".into(),
             tags: v2.tags.iter().cloned().map(From::from).collect(),
             info: v2.info.clone().into(),
-            servers: openapi3_servers(v2.schemes, v2.host, v2.base_path),
+            servers: if v2.servers.is_empty() {
+                openapi3_servers(v2.schemes, v2.host, v2.base_path)
+            } else {
+                v2.servers.into_iter().map(From::from).collect()
+            },
             external_docs: v2.external_docs.map(From::from),
             ..Default::default()
         };
@@ -24,6 +28,15 @@ impl From<v2::DefaultApiRaw> for openapiv3::OpenAPI {
                 i.insert(b.0.to_string(), b.1.clone().into());
                 i
             });
+        // Shared body and form parameters have no v3 counterpart in the components.
+        for (name, parameter) in &v2.parameters {
+            let either: Either<openapiv3::Parameter, _> = parameter.clone().into();
+            if let Either::Left(parameter) = either {
+                components
+                    .parameters
+                    .insert(name.clone(), openapiv3::ReferenceOr::Item(parameter));
+            }
+        }
         spec.extensions =
             v2.extensions
                 .into_iter()
@@ -31,30 +44,160 @@ impl From<v2::DefaultApiRaw> for openapiv3::OpenAPI {
                     i.insert(k, v);
                     i
                 });
+        let (consumes, produces) = (&v2.consumes, &v2.produces);
         spec.paths = openapiv3::Paths {
             paths: v2.paths.iter().fold(indexmap::IndexMap::new(), |mut i, b| {
-                i.insert(
-                    b.0.to_string(),
-                    openapiv3::ReferenceOr::Item(b.1.clone().into()),
-                );
+                let mut item = b.1.clone();
+                for op in item.methods.values_mut() {
+                    inherit_media_ranges(&mut op.consumes, consumes);
+                    inherit_media_ranges(&mut op.produces, produces);
+                }
+                i.insert(b.0.to_string(), openapiv3::ReferenceOr::Item(item.into()));
                 i
             }),
             ..Default::default()
         };
 
+        // Shared examples are emitted once, and referenced by the media types of their schemas.
+        let mut example_refs = std::collections::BTreeMap::new();
         components.schemas = v2
             .definitions
             .iter()
             .fold(indexmap::IndexMap::new(), |mut i, b| {
-                i.insert(b.0.to_string(), b.1.clone().into());
+                let mut schema = b.1.clone();
+                if let Some(name) = schema.example_ref.clone() {
+                    if let Some(value) = schema.example.take() {
+                        components.examples.insert(
+                            name.clone(),
+                            openapiv3::ReferenceOr::Item(openapiv3::Example {
+                                value: Some(value),
+                                ..Default::default()
+                            }),
+                        );
+                        let reference = b.0.replace('<', "%3C").replace('>', "%3E");
+                        example_refs.insert(format!("#/components/schemas/{}", reference), name);
+                    }
+                }
+                i.insert(b.0.to_string(), schema.into());
                 i
             });
+        if !example_refs.is_empty() {
+            for path in spec.paths.paths.values_mut() {
+                if let openapiv3::ReferenceOr::Item(path) = path {
+                    add_example_refs(path, &example_refs);
+                }
+            }
+        }
         spec.components = Some(components);
 
+        // The schemas are converted with 3.0 semantics (which is all `openapiv3` models), so
+        // the spec stays 3.0 and the (3.1) webhooks are emitted as the `x-webhooks` extension.
+        #[cfg(feature = "oas31")]
+        if !v2.webhooks.is_empty() {
+            let webhooks = v2
+                .webhooks
+                .into_iter()
+                .map(|(name, item)| (name, openapiv3::PathItem::from(item)))
+                .collect::<indexmap::IndexMap<_, _>>();
+            spec.extensions.insert(
+                WEBHOOKS_EXTENSION.into(),
+                serde_json::to_value(webhooks).expect("serializing webhooks"),
+            );
+        }
+
         spec
     }
 }
 
+/// Name of the extension holding the webhooks.
+#[cfg(feature = "oas31")]
+const WEBHOOKS_EXTENSION: &str = "x-webhooks";
+
+/// Operations without their own media ranges use the ones defined for the whole spec.
+fn inherit_media_ranges(
+    operation: &mut Option<std::collections::BTreeSet<v2::MediaRange>>,
+    defaults: &std::collections::BTreeSet<v2::MediaRange>,
+) {
+    let has_own = matches!(operation, Some(ranges) if !ranges.is_empty());
+    if !has_own && !defaults.is_empty() {
+        *operation = Some(defaults.clone());
+    }
+}
+
+/// References the shared examples from the media types whose schemas have them.
+fn add_example_refs(
+    path: &mut openapiv3::PathItem,
+    example_refs: &std::collections::BTreeMap<String, String>,
+) {
+    let operations = vec![
+        &mut path.get,
+        &mut path.put,
+        &mut path.post,
+        &mut path.delete,
+        &mut path.options,
+        &mut path.head,
+        &mut path.patch,
+        &mut path.trace,
+    ];
+    for op in operations.into_iter().flatten() {
+        let request_body = match &mut op.request_body {
+            Some(openapiv3::ReferenceOr::Item(body)) => Some(&mut body.content),
+            _ => None,
+        };
+        let responses = op
+            .responses
+            .default
+            .iter_mut()
+            .chain(op.responses.responses.values_mut())
+            .filter_map(|response| match response {
+                openapiv3::ReferenceOr::Item(response) => Some(&mut response.content),
+                openapiv3::ReferenceOr::Reference { .. } => None,
+            });
+        for media in request_body
+            .into_iter()
+            .chain(responses)
+            .flat_map(|content| content.values_mut())
+        {
+            if let Some(openapiv3::ReferenceOr::Reference { reference }) = &media.schema {
+                if let Some(name) = example_refs.get(reference) {
+                    media.examples.insert(
+                        name.clone(),
+                        openapiv3::ReferenceOr::ref_(&format!("#/components/examples/{}", name)),
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl From<v2::Server> for openapiv3::Server {
+    fn from(v2: v2::Server) -> Self {
+        openapiv3::Server {
+            url: v2.url,
+            description: v2.description,
+            variables: if v2.variables.is_empty() {
+                None
+            } else {
+                Some(
+                    v2.variables
+                        .into_iter()
+                        .map(|(name, var)| {
+                            let var = openapiv3::ServerVariable {
+                                enumeration: var.enum_,
+                                default: var.default,
+                                description: var.description,
+                                extensions: indexmap::IndexMap::new(),
+                            };
+                            (name, var)
+                        })
+                        .collect(),
+                )
+            },
+            extensions: indexmap::IndexMap::new(),
+        }
+    }
+}
+
 fn openapi3_servers(
     schemes: std::collections::BTreeSet<v2::OperationProtocol>,
     host: Option<String>,
@@ -23,22 +23,14 @@ impl From<v2::Operation<v2::DefaultParameterRaw, v2::DefaultResponseRaw>> for op
                                 None
                             }
                             Either::Right(Some(schema)) => {
-                                if let Some(any) = form_data.as_mut() {
-                                    any.properties.insert(
-                                        parameter.name.clone(),
-                                        openapiv3::ReferenceOr::Item(Box::new(schema)),
-                                    );
-                                } else {
-                                    let mut any = openapiv3::AnySchema::default();
-                                    if parameter.required {
-                                        any.required.push(parameter.name.clone());
-                                    }
-                                    any.properties.insert(
-                                        parameter.name.clone(),
-                                        openapiv3::ReferenceOr::Item(Box::new(schema)),
-                                    );
-                                    form_data = Some(any);
+                                let any = form_data.get_or_insert_with(Default::default);
+                                if parameter.required {
+                                    any.required.push(parameter.name.clone());
                                 }
+                                any.properties.insert(
+                                    parameter.name.clone(),
+                                    openapiv3::ReferenceOr::Item(Box::new(schema)),
+                                );
                                 None
                             }
                             Either::Right(None) => None,
@@ -49,7 +41,16 @@ impl From<v2::Operation<v2::DefaultParameterRaw, v2::DefaultResponseRaw>> for op
             })
             .collect();
 
-        let request_body = if let Some(request_body) = request_body {
+        let request_body = if let Some(mut request_body) = request_body {
+            // The body schema is shared by all the media types consumed by the operation.
+            if let Some(consumes) = v2.consumes.as_ref().filter(|c| !c.is_empty()) {
+                if let Some((_, media)) = request_body.content.pop() {
+                    request_body.content = consumes
+                        .iter()
+                        .map(|mime| (mime.0.to_string(), media.clone()))
+                        .collect();
+                }
+            }
             Some(openapiv3::ReferenceOr::Item(request_body))
         } else if let Some(form_data) = form_data {
             let mut request_body = openapiv3::RequestBody::default();
@@ -75,7 +76,7 @@ impl From<v2::Operation<v2::DefaultParameterRaw, v2::DefaultResponseRaw>> for op
             None
         };
 
-        openapiv3::Operation {
+        let mut operation = openapiv3::Operation {
             tags: v2.tags,
             summary: v2.summary,
             description: v2.description,
@@ -121,7 +122,34 @@ impl From<v2::Operation<v2::DefaultParameterRaw, v2::DefaultResponseRaw>> for op
                 )
             },
             servers: vec![],
-            extensions: indexmap::IndexMap::new(),
+            extensions: v2.extensions.into_iter().fold(
+                indexmap::IndexMap::new(),
+                |mut i, (k, v)| {
+                    i.insert(k, v);
+                    i
+                },
+            ),
+        };
+
+        // `openapiv3` doesn't model callbacks, so they're added through the flattened extensions.
+        if !v2.callbacks.is_empty() {
+            let callbacks = v2
+                .callbacks
+                .into_iter()
+                .map(|(name, callback)| {
+                    let callback = callback
+                        .into_iter()
+                        .map(|(expression, item)| (expression, openapiv3::PathItem::from(item)))
+                        .collect::<indexmap::IndexMap<_, _>>();
+                    (name, callback)
+                })
+                .collect::<indexmap::IndexMap<_, _>>();
+            operation.extensions.insert(
+                "callbacks".into(),
+                serde_json::to_value(callbacks).expect("serializing callbacks"),
+            );
         }
+
+        operation
     }
 }
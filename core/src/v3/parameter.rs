@@ -23,10 +23,12 @@ pub(crate) fn non_body_parameter_to_v3_parameter(
                                         openapiv3::IntegerFormat::Int64,
                                     )
                                 }
-                                other => {
-                                    debug_assert!(false, "Invalid data type format: {:?}", other);
+                                v2::DataTypeFormat::Other => {
                                     openapiv3::VariantOrUnknownOrEmpty::Empty
                                 }
+                                other => {
+                                    openapiv3::VariantOrUnknownOrEmpty::Unknown(other.to_string())
+                                }
                             },
                         },
                         multiple_of: v2.multiple_of.map(|v| v as i64),
@@ -57,10 +59,12 @@ pub(crate) fn non_body_parameter_to_v3_parameter(
                                         openapiv3::NumberFormat::Double {},
                                     )
                                 }
-                                other => {
-                                    debug_assert!(false, "Invalid data type format: {:?}", other);
+                                v2::DataTypeFormat::Other => {
                                     openapiv3::VariantOrUnknownOrEmpty::Empty
                                 }
+                                other => {
+                                    openapiv3::VariantOrUnknownOrEmpty::Unknown(other.to_string())
+                                }
                             },
                         },
                         multiple_of: v2.multiple_of.map(|v| v as f64),
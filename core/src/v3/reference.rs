@@ -13,7 +13,17 @@ impl<T> From<v2::Reference> for openapiv3::ReferenceOr<T> {
 }
 impl<T> From<&v2::Reference> for openapiv3::ReferenceOr<T> {
     fn from(v2: &v2::Reference) -> Self {
-        let reference = v2.reference.replace("definitions", "components/schemas");
-        openapiv3::ReferenceOr::ref_(&reference)
+        openapiv3::ReferenceOr::ref_(&v3_reference(&v2.reference))
+    }
+}
+
+/// Rewrites the `#/definitions/` (and `#/parameters/`) prefix of v2 references
+/// (e.g. those of the error schemas) to the v3 `#/components/schemas/` (and
+/// `#/components/parameters/`) one. Only the prefix is rewritten, so that
+/// definition names are left untouched.
+pub(crate) fn v3_reference(reference: &str) -> String {
+    match reference.strip_prefix("#/parameters/") {
+        Some(name) => format!("#/components/parameters/{}", name),
+        None => reference.replacen("#/definitions/", "#/components/schemas/", 1),
     }
 }
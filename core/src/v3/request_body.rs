@@ -4,39 +4,56 @@ impl From<v2::DefaultParameterRaw>
     for Either<openapiv3::Parameter, Either<openapiv3::RequestBody, Option<openapiv3::Schema>>>
 {
     fn from(v2: v2::DefaultParameterRaw) -> Self {
-        let parameter_data = |schema: Option<openapiv3::Schema>| openapiv3::ParameterData {
-            name: v2.name.clone(),
-            description: v2.description.clone(),
-            required: v2.required,
-            deprecated: None,
-            format: match &schema {
-                Some(schema) => openapiv3::ParameterSchemaOrContent::Schema(
-                    openapiv3::ReferenceOr::Item(schema.clone()),
-                ),
-                None => openapiv3::ParameterSchemaOrContent::Schema(invalid_referenceor(format!(
-                    "No Schema found: {:?}",
-                    v2
-                ))),
-            },
-            example: None,
-            examples: indexmap::IndexMap::new(),
-            explode: None,
-            extensions: indexmap::IndexMap::new(),
-        };
+        let parameter_data =
+            |schema: Option<openapiv3::ReferenceOr<openapiv3::Schema>>| {
+                openapiv3::ParameterData {
+                    name: v2.name.clone(),
+                    description: v2.description.clone(),
+                    required: v2.required,
+                    deprecated: if v2.deprecated { Some(true) } else { None },
+                    format: openapiv3::ParameterSchemaOrContent::Schema(schema.unwrap_or_else(
+                        || invalid_referenceor(format!("No Schema found: {:?}", v2)),
+                    )),
+                    example: v2.example.clone(),
+                    examples: indexmap::IndexMap::new(),
+                    // `deepObject` is only defined for exploded objects.
+                    explode: match v2.style {
+                        Some(v2::ParameterStyle::DeepObject) => Some(true),
+                        _ => None,
+                    },
+                    extensions: indexmap::IndexMap::new(),
+                }
+            };
+        let primitive_schema =
+            || non_body_parameter_to_v3_parameter(false, &v2).map(openapiv3::ReferenceOr::Item);
 
         match v2.in_ {
             v2::ParameterIn::Query => Either::Left(openapiv3::Parameter::Query {
-                parameter_data: parameter_data(non_body_parameter_to_v3_parameter(false, &v2)),
+                parameter_data: parameter_data(match v2.object_schema.clone() {
+                    Some(schema) => Some(schema.into()),
+                    None => primitive_schema(),
+                }),
                 allow_reserved: false,
-                style: Default::default(),
-                allow_empty_value: None,
+                style: match v2.style {
+                    None | Some(v2::ParameterStyle::Form) => openapiv3::QueryStyle::Form,
+                    Some(v2::ParameterStyle::SpaceDelimited) => {
+                        openapiv3::QueryStyle::SpaceDelimited
+                    }
+                    Some(v2::ParameterStyle::PipeDelimited) => openapiv3::QueryStyle::PipeDelimited,
+                    Some(v2::ParameterStyle::DeepObject) => openapiv3::QueryStyle::DeepObject,
+                },
+                allow_empty_value: if v2.allow_empty_value {
+                    Some(true)
+                } else {
+                    None
+                },
             }),
             v2::ParameterIn::Header => Either::Left(openapiv3::Parameter::Header {
-                parameter_data: parameter_data(non_body_parameter_to_v3_parameter(false, &v2)),
+                parameter_data: parameter_data(primitive_schema()),
                 style: Default::default(),
             }),
             v2::ParameterIn::Path => Either::Left(openapiv3::Parameter::Path {
-                parameter_data: parameter_data(non_body_parameter_to_v3_parameter(false, &v2)),
+                parameter_data: parameter_data(primitive_schema()),
                 style: Default::default(),
             }),
             v2::ParameterIn::FormData => {
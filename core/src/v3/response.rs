@@ -31,14 +31,33 @@ impl From<OperationResponse<'_>> for openapiv3::Response {
                             .unwrap_or_default();
                         let media = openapiv3::MediaType {
                             schema: Some(response.into()),
+                            examples: v2.response.examples.iter().fold(
+                                indexmap::IndexMap::new(),
+                                |mut i, (name, value)| {
+                                    i.insert(
+                                        name.clone(),
+                                        openapiv3::ReferenceOr::Item(openapiv3::Example {
+                                            value: Some(value.clone()),
+                                            ..Default::default()
+                                        }),
+                                    );
+                                    i
+                                },
+                            ),
                             ..Default::default()
                         };
 
                         let mut map = indexmap::IndexMap::new();
-                        match v2.operation.produces.as_ref() {
+                        let produces = v2.response.produces.as_ref();
+                        match produces.or(v2.operation.produces.as_ref()) {
                             Some(range) => {
                                 for mime in range {
-                                    map.insert(mime.0.to_string(), media.clone());
+                                    let mime = mime.0.to_string();
+                                    let mut media = media.clone();
+                                    if let Some(schema) = v2.response.content_schemas.get(&mime) {
+                                        media.schema = Some(schema.clone().into());
+                                    }
+                                    map.insert(mime, media);
                                 }
                             }
                             None => {
@@ -56,7 +75,26 @@ impl From<OperationResponse<'_>> for openapiv3::Response {
                 }
             },
             extensions: indexmap::IndexMap::new(),
-            links: indexmap::IndexMap::new(),
+            links: v2
+                .response
+                .links
+                .iter()
+                .map(|(name, link)| {
+                    let link = openapiv3::Link {
+                        description: link.description.clone(),
+                        operation: openapiv3::LinkOperation::OperationId(link.operation_id.clone()),
+                        request_body: None,
+                        parameters: link
+                            .parameters
+                            .iter()
+                            .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
+                            .collect(),
+                        server: None,
+                        extensions: indexmap::IndexMap::new(),
+                    };
+                    (name.clone(), openapiv3::ReferenceOr::Item(link))
+                })
+                .collect(),
         }
     }
 }
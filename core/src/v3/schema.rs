@@ -16,32 +16,100 @@ impl From<v2::DefaultSchemaRaw> for openapiv3::ReferenceOr<Box<openapiv3::Schema
 impl From<v2::DefaultSchemaRaw> for openapiv3::ReferenceOr<openapiv3::Schema> {
     fn from(v2: v2::DefaultSchemaRaw) -> Self {
         match v2.reference.clone() {
+            Some(_) if v2.nullable => nullable_reference(v2),
             Some(reference) => v2::Reference { reference }.into(),
             None => {
                 let item = openapiv3::Schema {
                     schema_data: openapiv3::SchemaData {
-                        nullable: false,
-                        read_only: false,
-                        write_only: false,
-                        deprecated: false,
+                        nullable: v2.nullable,
+                        read_only: v2.read_only,
+                        write_only: v2.write_only,
+                        deprecated: v2.deprecated,
                         external_docs: None,
                         example: v2.example,
                         title: v2.title,
                         description: v2.description,
                         discriminator: None,
                         default: None,
-                        extensions: indexmap::IndexMap::new(),
+                        // `openapiv3` has no XML object, but the extensions are flattened
+                        // into the schema, so it ends up in the right place anyway.
+                        extensions: v2
+                            .extensions
+                            .into_iter()
+                            .chain(v2.xml.and_then(|xml| {
+                                serde_json::to_value(xml).ok().map(|v| ("xml".into(), v))
+                            }))
+                            .fold(indexmap::IndexMap::new(), |mut i, (k, v)| {
+                                i.insert(k, v);
+                                i
+                            }),
                     },
                     schema_kind: {
-                        if let Some(data_type) = v2.data_type {
-                            v2_data_type_to_v3(
+                        // Composite schemas keep the wrapper's metadata (description, title,
+                        // example, etc.) in `schema_data` above.
+                        if !v2.all_of.is_empty() {
+                            // A nullable `allOf` already wraps its references, so they
+                            // aren't wrapped (again) on their own.
+                            let nullable = v2.nullable;
+                            openapiv3::SchemaKind::AllOf {
+                                all_of: v2
+                                    .all_of
+                                    .into_iter()
+                                    .map(|mut s| {
+                                        if nullable && s.reference.is_some() {
+                                            s.nullable = false;
+                                        }
+                                        (*s).into()
+                                    })
+                                    .collect(),
+                            }
+                        } else if !v2.one_of.is_empty() {
+                            openapiv3::SchemaKind::OneOf {
+                                one_of: v2.one_of.into_iter().map(|s| (*s).into()).collect(),
+                            }
+                        } else if !v2.any_of.is_empty() {
+                            openapiv3::SchemaKind::AnyOf {
+                                any_of: v2.any_of.into_iter().map(|s| (*s).into()).collect(),
+                            }
+                        } else if let Some(data_type) = v2.data_type {
+                            let mut kind = v2_data_type_to_v3(
                                 &data_type,
                                 &v2.format,
                                 &v2.enum_,
                                 &v2.items,
                                 &v2.properties,
                                 &v2.required,
-                            )
+                            );
+                            if let openapiv3::SchemaKind::Type(openapiv3::Type::Array(array)) =
+                                &mut kind
+                            {
+                                array.min_items = v2.min_items.map(|v| v as usize);
+                                array.max_items = v2.max_items.map(|v| v as usize);
+                                array.unique_items = v2.unique_items;
+                            }
+                            if let openapiv3::SchemaKind::Type(openapiv3::Type::String(string)) =
+                                &mut kind
+                            {
+                                string.pattern = v2.pattern;
+                                string.min_length = v2.min_length.map(|v| v as usize);
+                                string.max_length = v2.max_length.map(|v| v as usize);
+                            }
+                            if let openapiv3::SchemaKind::Type(openapiv3::Type::Integer(integer)) =
+                                &mut kind
+                            {
+                                integer.minimum = v2.minimum.map(|v| v as i64);
+                                integer.maximum = v2.maximum.map(|v| v as i64);
+                            }
+                            if let openapiv3::SchemaKind::Type(openapiv3::Type::Number(number)) =
+                                &mut kind
+                            {
+                                number.minimum = v2.minimum.map(f64::from);
+                                number.maximum = v2.maximum.map(f64::from);
+                            }
+                            kind
+                        } else if v2.properties.is_empty() {
+                            // Free-form values (e.g., `serde_json::Value`) accept anything.
+                            openapiv3::SchemaKind::Any(openapiv3::AnySchema::default())
                         } else {
                             openapiv3::SchemaKind::Type(openapiv3::Type::Object(
                                 openapiv3::ObjectType::default(),
@@ -55,6 +123,20 @@ impl From<v2::DefaultSchemaRaw> for openapiv3::ReferenceOr<openapiv3::Schema> {
     }
 }
 
+/// Siblings of `$ref` are ignored in OpenAPI 3.0, so a nullable reference is wrapped in
+/// an `allOf`, which carries the `nullable` flag (and any other metadata) instead.
+fn nullable_reference(mut v2: v2::DefaultSchemaRaw) -> openapiv3::ReferenceOr<openapiv3::Schema> {
+    let reference = v2::DefaultSchemaRaw {
+        reference: v2.reference.take(),
+        ..Default::default()
+    };
+    v2::DefaultSchemaRaw {
+        all_of: vec![Box::new(reference)],
+        ..v2
+    }
+    .into()
+}
+
 // helper function to convert a v2 DataType to v3, with explicit types making it more
 // rust-analyzer friendly as the DefaultSchemaRaw is autogenerated by a macro
 fn v2_data_type_to_v3(
@@ -77,10 +159,8 @@ fn v2_data_type_to_v3(
                         v2::DataTypeFormat::Int64 => openapiv3::VariantOrUnknownOrEmpty::Item(
                             openapiv3::IntegerFormat::Int64,
                         ),
-                        other => {
-                            debug_assert!(false, "Invalid data type format: {:?}", other);
-                            openapiv3::VariantOrUnknownOrEmpty::Empty
-                        }
+                        v2::DataTypeFormat::Other => openapiv3::VariantOrUnknownOrEmpty::Empty,
+                        other => openapiv3::VariantOrUnknownOrEmpty::Unknown(other.to_string()),
                     },
                 },
                 multiple_of: None,
@@ -106,10 +186,8 @@ fn v2_data_type_to_v3(
                         v2::DataTypeFormat::Double => openapiv3::VariantOrUnknownOrEmpty::Item(
                             openapiv3::NumberFormat::Double {},
                         ),
-                        other => {
-                            debug_assert!(false, "Invalid data type format: {:?}", other);
-                            openapiv3::VariantOrUnknownOrEmpty::Empty
-                        }
+                        v2::DataTypeFormat::Other => openapiv3::VariantOrUnknownOrEmpty::Empty,
+                        other => openapiv3::VariantOrUnknownOrEmpty::Unknown(other.to_string()),
                     },
                 },
                 multiple_of: None,
@@ -6,7 +6,13 @@ impl From<v2::Tag> for openapiv3::Tag {
             name: v2.name,
             description: v2.description,
             external_docs: v2.external_docs.map(openapiv3::ExternalDocumentation::from),
-            extensions: indexmap::IndexMap::new(),
+            extensions: {
+                let mut extensions = indexmap::IndexMap::new();
+                if v2.deprecated {
+                    extensions.insert("x-tag-deprecated".into(), serde_json::Value::Bool(true));
+                }
+                extensions
+            },
         }
     }
 }
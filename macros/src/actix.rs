@@ -5,26 +5,32 @@ use heck::*;
 use http::StatusCode;
 use lazy_static::lazy_static;
 use proc_macro::TokenStream;
-use quote::{quote, ToTokens};
+use quote::{quote, quote_spanned, ToTokens};
 use strum_macros::EnumString;
 use syn::{
+    parse::{Parse, ParseStream},
     parse_macro_input,
     punctuated::{Pair, Punctuated},
     spanned::Spanned,
     Attribute, Data, DataEnum, DeriveInput, Field, Fields, FieldsNamed, FieldsUnnamed, FnArg,
-    Generics, Ident, ItemFn, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path, PathArguments,
-    ReturnType, Token, TraitBound, Type, TypeTraitObject,
+    GenericArgument, GenericParam, Generics, Ident, ItemFn, Lit, LitStr, Meta, MetaList,
+    MetaNameValue, NestedMeta, Path, PathArguments, ReturnType, Token, TraitBound, Type,
+    TypeTraitObject, Variant,
 };
 
 use proc_macro2::TokenStream as TokenStream2;
 use std::collections::HashMap;
 
 const SCHEMA_MACRO_ATTR: &str = "openapi";
+/// Name of the extension holding the preview flag of gated operations.
+const PREVIEW_GATE_EXTENSION: &str = "x_fp_preview_gate";
+/// Name of the extension holding the display priority of enum variants.
+const PRIORITY_EXTENSION: &str = "x_fp_priority";
 
 lazy_static! {
     static ref EMPTY_SCHEMA_HELP: String = format!(
-        "you can mark the struct with #[{}(empty)] to ignore this warning.",
-        SCHEMA_MACRO_ATTR
+        "you can mark the struct with #[{}(empty)] (or #[{}(empty = \"reason\")]) to ignore this warning.",
+        SCHEMA_MACRO_ATTR, SCHEMA_MACRO_ATTR
     );
 }
 
@@ -139,7 +145,8 @@ pub fn emit_v2_operation(attrs: TokenStream, input: TokenStream) -> TokenStream
     );
 
     // Initialize operation parameters from macro attributes
-    let (mut op_params, mut op_values) = parse_operation_attrs(attrs);
+    let (mut op_params, mut op_values, responses, callbacks, deprecated_if) =
+        parse_operation_attrs(attrs);
 
     if op_params.iter().any(|i| *i == "skip") {
         return quote!(
@@ -182,11 +189,142 @@ pub fn emit_v2_operation(attrs: TokenStream, input: TokenStream) -> TokenStream
         }
     }
 
-    if op_params.iter().any(|i| *i == "deprecated") || extract_deprecated(&item_ast.attrs) {
+    // Operations can also be deprecated conditionally, based on the `cfg` of the crate
+    // using the macro (e.g., for sunset schedules controlled by features).
+    let deprecated = if op_params.iter().any(|i| *i == "deprecated") {
+        op_params.retain(|i| *i != "deprecated");
+        Some(quote!(true))
+    } else if extract_deprecated(&item_ast.attrs) {
+        Some(quote!(true))
+    } else {
+        deprecated_if.map(|predicate| quote!(cfg!(#predicate)))
+    };
+    if let Some(deprecated) = deprecated {
         op_params.push(Ident::new("deprecated", item_ast.span()));
-        op_values.push(quote!(true))
+        op_values.push(deprecated)
     }
 
+    // Internal operations are only documented in internal builds, and skipped otherwise.
+    let is_visible = if op_params.iter().any(|i| *i == "internal") {
+        op_params.retain(|i| *i != "internal");
+        cfg!(feature = "internal-operations")
+    } else {
+        true
+    };
+
+    // Request bodies are dropped for `GET`, `DELETE` and `HEAD`, unless explicitly allowed.
+    let allow_get_body = op_params.iter().any(|i| *i == "allow_get_body");
+    op_params.retain(|i| *i != "allow_get_body");
+
+    // `no_content` handlers only ever respond with an empty 204.
+    let no_content = if op_params.iter().any(|i| *i == "no_content") {
+        op_params.retain(|i| *i != "no_content");
+        quote!(
+            op.responses.remove("200");
+            op.responses.insert(
+                "204".into(),
+                paperclip::v2::models::Either::Right(paperclip::v2::models::Response {
+                    description: Some("No Content".into()),
+                    ..Default::default()
+                }),
+            );
+        )
+    } else {
+        quote!()
+    };
+
+    // Swagger 2.0 has a single response schema, so the first media type's schema is used there.
+    let media_schemas = match responses.media_types.first() {
+        Some((first_mime, _)) => {
+            let (mimes, types): (Vec<_>, Vec<_>) = responses.media_types.iter().cloned().unzip();
+            quote!(
+                if let paperclip::v2::models::Either::Right(response) =
+                    op.responses.entry("200".into()).or_insert_with(|| {
+                        paperclip::v2::models::Either::Right(paperclip::v2::models::Response {
+                            description: Some("OK".into()),
+                            ..Default::default()
+                        })
+                    })
+                {
+                    #(
+                        response.content_schemas.insert(#mimes.into(), {
+                            let mut def = <#types as paperclip::v2::schema::Apiv2Schema>::schema_with_ref();
+                            def.retain_ref();
+                            def
+                        });
+                    )*
+                    if response.schema.is_none() {
+                        response.schema = response.content_schemas.get(#first_mime).cloned();
+                    }
+                }
+            )
+        }
+        None => quote!(),
+    };
+    let media_types = responses
+        .media_types
+        .iter()
+        .map(|(_, ty)| ty)
+        .collect::<Vec<_>>();
+
+    let success_description = match &responses.success_description {
+        Some(description) => quote!(
+            if let Some(paperclip::v2::models::Either::Right(response)) = op.responses.get_mut("200") {
+                response.description = Some(#description.into());
+            }
+        ),
+        None => quote!(),
+    };
+
+    let links = responses.links.iter().map(|link| {
+        let code_matches = match link.code {
+            Some(code) => {
+                let code = code.to_string();
+                quote!(*code == #code)
+            }
+            None => quote!(code.starts_with('2')),
+        };
+        let (name, operation_id) = (&link.name, &link.operation_id);
+        let (param_names, param_values): (Vec<_>, Vec<_>) = link.parameters.iter().cloned().unzip();
+        let description = match &link.description {
+            Some(d) => quote!(Some(#d.into())),
+            None => quote!(None),
+        };
+        quote!(
+            if let Some((_, paperclip::v2::models::Either::Right(response))) =
+                op.responses.iter_mut().find(|(code, _)| #code_matches)
+            {
+                response.links.insert(
+                    #name.into(),
+                    paperclip::v2::models::Link {
+                        operation_id: #operation_id.into(),
+                        parameters: vec![#( (#param_names.into(), #param_values.into()) ),*]
+                            .into_iter()
+                            .collect(),
+                        description: #description,
+                    },
+                );
+            }
+        )
+    });
+
+    // Callbacks reuse the operations (and definitions) of the handlers describing them.
+    let callback_ops = callbacks.iter().map(|c| {
+        let (name, path, method, handler) = (&c.name, &c.path, &c.method, &c.handler);
+        quote!(
+            op.add_callback(
+                #name,
+                #path,
+                paperclip::v2::models::HttpMethod::#method,
+                <#handler as paperclip::v2::schema::Apiv2Operation>::operation(),
+            );
+        )
+    });
+    let callback_handlers = callbacks.iter().map(|c| &c.handler).collect::<Vec<_>>();
+
+    let (_, code_samples) = extract_code_samples(&extract_documentation(&item_ast.attrs));
+    let (sample_langs, sample_sources): (Vec<_>, Vec<_>) = code_samples.into_iter().unzip();
+
     let modifiers = extract_fn_arguments_types(&item_ast);
 
     let operation_modifier = if is_responder {
@@ -195,11 +333,104 @@ pub fn emit_v2_operation(attrs: TokenStream, input: TokenStream) -> TokenStream
         quote! { <<#wrapper as std::future::Future>::Output> }
     };
 
+    // Explicitly declared success responses (e.g., for handlers building `HttpResponse` by hand).
+    let (response_codes, response_descriptions): (Vec<_>, Vec<_>) = responses
+        .typed
+        .iter()
+        .map(|(code, _)| {
+            let description = StatusCode::from_u16(*code)
+                .ok()
+                .and_then(|s| s.canonical_reason())
+                .unwrap_or_default();
+            (code.to_string(), description)
+        })
+        .unzip();
+    let response_types = responses.typed.iter().map(|(_, ty)| ty).collect::<Vec<_>>();
+    let (described_codes, described_descriptions): (Vec<_>, Vec<_>) = responses
+        .described
+        .into_iter()
+        .map(|(code, description)| (code.to_string(), description))
+        .unzip();
+    // Named examples belong to the (first) success response.
+    let examples = if responses.examples.is_empty() {
+        quote!()
+    } else {
+        let (names, values): (Vec<_>, Vec<_>) = responses.examples.into_iter().unzip();
+        quote!(
+            if let Some((_, paperclip::v2::models::Either::Right(response))) =
+                op.responses.iter_mut().find(|(code, _)| code.starts_with('2'))
+            {
+                #(
+                    response.examples.insert(
+                        #names.into(),
+                        serde_json::from_str::<serde_json::Value>(#values)
+                            .unwrap_or_else(|_| #values.into()),
+                    );
+                )*
+            }
+        )
+    };
+
+    // Response headers also belong to the success response, which is the 204 for `no_content`.
+    let response_headers = if responses.headers.is_empty() {
+        quote!()
+    } else {
+        for (name, _) in &responses.header_types {
+            if !responses
+                .headers
+                .iter()
+                .any(|(n, _)| n == name.value().as_str())
+            {
+                emit_error!(
+                    name.span(),
+                    "Header {} has a type but isn't declared in `headers`",
+                    name.value()
+                );
+            }
+        }
+        let header_types = &responses.header_types;
+        let (names, descriptions): (Vec<_>, Vec<_>) = responses.headers.into_iter().unzip();
+        let types =
+            names.iter().map(
+                |name| match header_types.iter().find(|(n, _)| n.value() == *name) {
+                    Some((_, ty)) => quote!(
+                        data_type: Some(<#ty as paperclip::v2::schema::TypedData>::data_type()),
+                        format: <#ty as paperclip::v2::schema::TypedData>::format(),
+                    ),
+                    None => quote!(data_type: Some(paperclip::v2::models::DataType::String),),
+                },
+            );
+        quote!(
+            if let Some((_, paperclip::v2::models::Either::Right(response))) =
+                op.responses.iter_mut().find(|(code, _)| code.starts_with('2'))
+            {
+                #(
+                    response.headers.insert(
+                        #names.into(),
+                        paperclip::v2::models::Header {
+                            description: Some(#descriptions.into()),
+                            #types
+                            ..Default::default()
+                        },
+                    );
+                )*
+            }
+        )
+    };
+
     quote!(
         #struct_definition
 
         #item_ast
 
+        impl #impl_generics #unit_struct #ty_generics #where_clause {
+            /// Returns the OpenAPI operation documented for this handler.
+            #[allow(dead_code)]
+            pub fn raw_operation() -> paperclip::v2::models::DefaultOperationRaw {
+                <Self as paperclip::v2::schema::Apiv2Operation>::operation()
+            }
+        }
+
         impl #impl_generics paperclip::v2::schema::Apiv2Operation for #unit_struct #ty_generics #where_clause {
             fn operation() -> paperclip::v2::models::DefaultOperationRaw {
                 use paperclip::actix::OperationModifier;
@@ -207,13 +438,47 @@ pub fn emit_v2_operation(attrs: TokenStream, input: TokenStream) -> TokenStream
                     #(
                         #op_params: #op_values,
                     )*
+                    allow_get_body: #allow_get_body,
                     .. Default::default()
                 };
+                #(
+                    op.add_code_sample(#sample_langs, #sample_sources);
+                )*
                 #(
                     <#modifiers>::update_parameter(&mut op);
                     <#modifiers>::update_security(&mut op);
                 )*
                 #operation_modifier::update_response(&mut op);
+                #no_content
+                #(
+                    op.responses.insert(
+                        #response_codes.into(),
+                        paperclip::v2::models::Either::Right(paperclip::v2::models::Response {
+                            description: Some(#response_descriptions.into()),
+                            schema: Some({
+                                let mut def = <#response_types as paperclip::v2::schema::Apiv2Schema>::schema_with_ref();
+                                def.retain_ref();
+                                def
+                            }),
+                            ..Default::default()
+                        }),
+                    );
+                )*
+                #examples
+                #(
+                    if let paperclip::v2::models::Either::Right(response) = op
+                        .responses
+                        .entry(#described_codes.into())
+                        .or_insert_with(|| paperclip::v2::models::Either::Right(Default::default()))
+                    {
+                        response.description = Some(#described_descriptions.into());
+                    }
+                )*
+                #media_schemas
+                #success_description
+                #response_headers
+                #(#links)*
+                #(#callback_ops)*
                 op
             }
 
@@ -234,8 +499,21 @@ pub fn emit_v2_operation(attrs: TokenStream, input: TokenStream) -> TokenStream
                     <#modifiers>::update_definitions(&mut map);
                 )*
                 #operation_modifier::update_definitions(&mut map);
+                #(
+                    <paperclip::actix::web::Json<#response_types>>::update_definitions(&mut map);
+                )*
+                #(
+                    <paperclip::actix::web::Json<#media_types>>::update_definitions(&mut map);
+                )*
+                #(
+                    map.extend(<#callback_handlers as paperclip::v2::schema::Apiv2Operation>::definitions());
+                )*
                 map
             }
+
+            fn is_visible() -> bool {
+                #is_visible
+            }
         }
     )
         .into()
@@ -275,15 +553,142 @@ fn extract_fn_arguments_types(item_ast: &ItemFn) -> Vec<Type> {
 /// Returning operation attribute identifier and value initialization arrays
 /// Note: Array likes initialized from string "val1, val2, val3", where "val1"
 /// would parse into destination item
-fn parse_operation_attrs(attrs: TokenStream) -> (Vec<Ident>, Vec<proc_macro2::TokenStream>) {
-    let attrs = crate::parse_input_attrs(attrs);
+/// Explicitly declared responses, callbacks and the `cfg` predicate of `deprecated_if`
+/// are returned separately.
+fn parse_operation_attrs(
+    attrs: TokenStream,
+) -> (
+    Vec<Ident>,
+    Vec<proc_macro2::TokenStream>,
+    OperationResponses,
+    Vec<OperationCallback>,
+    Option<Meta>,
+) {
+    let attrs: MacroAttrs = syn::parse(attrs)
+        .map_err(|e| {
+            emit_warning!(
+                e.span().unwrap(),
+                "cannot parse proc-macro input attributes."
+            );
+        })
+        .ok()
+        .unwrap_or_default();
     let mut params = Vec::new();
     let mut values = Vec::new();
+    let mut responses = OperationResponses::default();
+    let mut callbacks = Vec::new();
+    let mut deprecated_if = None;
+    let mut extensions = Vec::new();
+    let mut is_stream = false;
     for attr in attrs.0 {
+        let attr = match attr {
+            MacroAttr::Pairs(ident, list) if ident == "responses" => {
+                for (code, ty) in list {
+                    match (&code, &ty) {
+                        (Lit::Int(code), Lit::Str(ty)) => {
+                            match (code.base10_parse::<u16>(), ty.parse::<Type>()) {
+                                (Ok(code), Ok(ty)) => responses.typed.push((code, ty)),
+                                (Err(_), _) => {
+                                    emit_error!(code.span(), "Invalid u16 in status code")
+                                }
+                                (_, Err(err)) => emit_error!(
+                                    ty.span(),
+                                    "Value {} does not parse as type: {}",
+                                    ty.value(),
+                                    err
+                                ),
+                            }
+                        }
+                        _ => emit_error!(
+                            code.span(),
+                            "Expected status code mapped to string literal type, e.g. 200 = \"Foo\""
+                        ),
+                    }
+                }
+                continue;
+            }
+            MacroAttr::Pairs(ident, list) if ident == "examples" => {
+                for (name, value) in list {
+                    match (&name, &value) {
+                        (Lit::Str(name), Lit::Str(value)) => {
+                            responses.examples.push((name.value(), value.value()))
+                        }
+                        _ => emit_error!(
+                            name.span(),
+                            "Expected example name mapped to string literal, e.g. \"cat\" = \"...\""
+                        ),
+                    }
+                }
+                continue;
+            }
+            MacroAttr::Pairs(ident, list) if ident == "headers" => {
+                for (name, description) in list {
+                    match (&name, &description) {
+                        (Lit::Str(name), Lit::Str(description)) => responses
+                            .headers
+                            .push((name.value(), description.value())),
+                        _ => emit_error!(
+                            name.span(),
+                            "Expected header name mapped to string literal description, e.g. \"Location\" = \"...\""
+                        ),
+                    }
+                }
+                continue;
+            }
+            MacroAttr::Pairs(ident, list) if ident == "header_types" => {
+                for (name, ty) in list {
+                    match (&name, &ty) {
+                        (Lit::Str(name), Lit::Str(ty)) => match ty.parse::<Type>() {
+                            Ok(ty) => responses.header_types.push((name.clone(), ty)),
+                            Err(err) => emit_error!(
+                                ty.span(),
+                                "Value {} does not parse as type: {}",
+                                ty.value(),
+                                err
+                            ),
+                        },
+                        _ => emit_error!(
+                            name.span(),
+                            "Expected header name mapped to string literal type, e.g. \"X-Rate-Limit\" = \"u32\""
+                        ),
+                    }
+                }
+                continue;
+            }
+            MacroAttr::Pairs(ident, list) if ident == "extension" => {
+                extensions.extend(extension_pairs(list));
+                continue;
+            }
+            MacroAttr::Pairs(ident, list) if ident == "produces" => {
+                // Content negotiation, e.g. `produces("text/csv" = "Report")`.
+                for (mime, ty) in list {
+                    match (&mime, &ty) {
+                        (Lit::Str(mime), Lit::Str(ty)) => {
+                            push_media_type(&mut responses, mime.value(), ty)
+                        }
+                        _ => emit_error!(
+                            mime.span(),
+                            "Expected media type mapped to string literal type, e.g. \"text/csv\" = \"Foo\""
+                        ),
+                    }
+                }
+                continue;
+            }
+            MacroAttr::Pairs(ident, _) => {
+                emit_error!(ident.span(), "Unknown list ident {}", ident);
+                continue;
+            }
+            MacroAttr::Meta(attr) => attr,
+        };
         match &attr {
             NestedMeta::Meta(Meta::Path(attr_path)) => {
                 if let Some(attr_) = attr_path.get_ident() {
-                    if *attr_ == "skip" || *attr_ == "deprecated" {
+                    if *attr_ == "skip"
+                        || *attr_ == "deprecated"
+                        || *attr_ == "no_content"
+                        || *attr_ == "internal"
+                        || *attr_ == "allow_get_body"
+                    {
                         params.push(attr_.clone());
                     } else {
                         emit_error!(attr_.span(), "Not supported bare attribute {:?}", attr_)
@@ -301,6 +706,55 @@ fn parse_operation_attrs(attrs: TokenStream) -> (Vec<Ident>, Vec<proc_macro2::To
                                 emit_error!(lit.span(), "Expected string literal: {:?}", lit)
                             }
                         }
+                        "response" | "stream_item" => {
+                            if let Lit::Str(val) = lit {
+                                is_stream |= *ident == "stream_item";
+                                match val.parse::<Type>() {
+                                    Ok(ty) => responses.typed.push((200, ty)),
+                                    Err(err) => emit_error!(
+                                        lit.span(),
+                                        "Value {} does not parse as type: {}",
+                                        val.value(),
+                                        err
+                                    ),
+                                }
+                            } else {
+                                emit_error!(lit.span(), "Expected string literal: {:?}", lit)
+                            }
+                        }
+                        "gated" => {
+                            // Operations behind a preview flag are marked for the docs site.
+                            if let Lit::Str(val) = lit {
+                                extensions.push((
+                                    LitStr::new(PREVIEW_GATE_EXTENSION, val.span()),
+                                    lit.clone(),
+                                ));
+                            } else {
+                                emit_error!(lit.span(), "Expected string literal: {:?}", lit)
+                            }
+                        }
+                        "deprecated_if" => {
+                            // A `cfg` predicate, e.g. `deprecated_if = "feature = \"v2-sunset\""`.
+                            match lit {
+                                Lit::Str(val) => match val.parse::<Meta>() {
+                                    Ok(predicate) => deprecated_if = Some(predicate),
+                                    Err(err) => emit_error!(
+                                        lit.span(),
+                                        "Value {} does not parse as cfg predicate: {}",
+                                        val.value(),
+                                        err
+                                    ),
+                                },
+                                _ => emit_error!(lit.span(), "Expected string literal: {:?}", lit),
+                            }
+                        }
+                        "success_description" => {
+                            if let Lit::Str(val) = lit {
+                                responses.success_description = Some(val.value());
+                            } else {
+                                emit_error!(lit.span(), "Expected string literal: {:?}", lit)
+                            }
+                        }
                         "consumes" | "produces" => {
                             if let Lit::Str(mimes) = lit {
                                 let mut mime_types = Vec::new();
@@ -368,6 +822,158 @@ fn parse_operation_attrs(attrs: TokenStream) -> (Vec<Ident>, Vec<proc_macro2::To
                                 values.push(quote!(vec![ #( #tags.to_string() ),* ]));
                             }
                         }
+                        "security" => {
+                            // Requirements are alternatives (OR) of scheme groups (AND).
+                            let mut requirements: Vec<Vec<String>> = Vec::new();
+                            for meta in nested.pairs().map(|pair| pair.into_value()) {
+                                match meta {
+                                    NestedMeta::Lit(Lit::Str(lit)) => {
+                                        requirements.push(vec![lit.value()])
+                                    }
+                                    NestedMeta::Meta(Meta::List(list))
+                                        if list.path.is_ident("all") || list.path.is_ident("any") =>
+                                    {
+                                        let mut schemes = Vec::new();
+                                        for scheme in &list.nested {
+                                            if let NestedMeta::Lit(Lit::Str(lit)) = scheme {
+                                                schemes.push(lit.value());
+                                            } else {
+                                                emit_error!(
+                                                    scheme.span(),
+                                                    "Expected security scheme name as string literal: {:?}",
+                                                    scheme
+                                                )
+                                            }
+                                        }
+                                        if list.path.is_ident("all") {
+                                            requirements.push(schemes);
+                                        } else {
+                                            requirements.extend(schemes.into_iter().map(|s| vec![s]));
+                                        }
+                                    }
+                                    _ => emit_error!(
+                                        meta.span(),
+                                        "Expected security scheme names or all(...)/any(...) of them: {:?}",
+                                        meta
+                                    ),
+                                }
+                            }
+                            if !requirements.is_empty() {
+                                params.push(ident.clone());
+                                values.push(quote!(vec![
+                                    #({
+                                        let mut map = std::collections::BTreeMap::new();
+                                        #(
+                                            map.insert(#requirements.to_string(), vec![]);
+                                        )*
+                                        map
+                                    }),*
+                                ]));
+                            }
+                        }
+                        "examples" => {
+                            for meta in nested.pairs().map(|pair| pair.into_value()) {
+                                match meta {
+                                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                                        path,
+                                        lit: Lit::Str(value),
+                                        ..
+                                    })) if path.get_ident().is_some() => {
+                                        let name = path.get_ident().unwrap().to_string();
+                                        responses.examples.push((name, value.value()));
+                                    }
+                                    _ => emit_error!(
+                                        meta.span(),
+                                        "Expected example names mapped to string literals, e.g. cat = \"...\": {:?}",
+                                        meta
+                                    ),
+                                }
+                            }
+                        }
+                        "response" => {
+                            // Documentation-only responses, e.g. `response(302, description = "...")`.
+                            let mut code = None;
+                            let mut description = None;
+                            for meta in nested.pairs().map(|pair| pair.into_value()) {
+                                match meta {
+                                    NestedMeta::Lit(Lit::Int(lit)) if code.is_none() => {
+                                        match lit.base10_parse::<u16>() {
+                                            Ok(c) => code = Some(c),
+                                            Err(_) => {
+                                                emit_error!(lit.span(), "Invalid u16 in status code")
+                                            }
+                                        }
+                                    }
+                                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                                        path,
+                                        lit: Lit::Str(lit),
+                                        ..
+                                    })) if path.is_ident("description") => {
+                                        description = Some(lit.value())
+                                    }
+                                    _ => emit_error!(
+                                        meta.span(),
+                                        "Expected status code and description, e.g. response(302, description = \"Redirect\"): {:?}",
+                                        meta
+                                    ),
+                                }
+                            }
+                            match code {
+                                Some(code) => {
+                                    let description = description.unwrap_or_else(|| {
+                                        StatusCode::from_u16(code)
+                                            .ok()
+                                            .and_then(|s| s.canonical_reason())
+                                            .unwrap_or_default()
+                                            .to_string()
+                                    });
+                                    responses.described.push((code, description));
+                                }
+                                None => emit_error!(
+                                    ident.span(),
+                                    "Expected status code in response(...)"
+                                ),
+                            }
+                        }
+                        "callback" => {
+                            if let Some(callback) = parse_callback(ident, nested) {
+                                callbacks.push(callback);
+                            }
+                        }
+                        "link" => {
+                            if let Some(link) = parse_link(ident, nested) {
+                                responses.links.push(link);
+                            }
+                        }
+                        "produces" => {
+                            // Content negotiation, e.g. `produces(json = "Foo", xml = "Bar")`.
+                            for meta in nested.pairs().map(|pair| pair.into_value()) {
+                                match meta {
+                                    NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                                        path,
+                                        lit: Lit::Str(ty),
+                                        ..
+                                    })) if path.get_ident().is_some() => {
+                                        let name = path.get_ident().unwrap().to_string();
+                                        match media_type_shorthand(&name) {
+                                            Some(mime) => {
+                                                push_media_type(&mut responses, mime.into(), ty)
+                                            }
+                                            None => emit_error!(
+                                                path.span(),
+                                                "Unknown media type {}, use the media type instead, e.g. \"text/csv\" = \"Foo\"",
+                                                name
+                                            ),
+                                        }
+                                    }
+                                    _ => emit_error!(
+                                        meta.span(),
+                                        "Expected media types mapped to string literal types, e.g. json = \"Foo\": {:?}",
+                                        meta
+                                    ),
+                                }
+                            }
+                        }
                         x => emit_error!(ident.span(), "Unknown list ident {}", x),
                     }
                 }
@@ -377,7 +983,312 @@ fn parse_operation_attrs(attrs: TokenStream) -> (Vec<Ident>, Vec<proc_macro2::To
             }
         }
     }
-    (params, values)
+    // Negotiated media types are all produced, unless explicitly set.
+    if !responses.media_types.is_empty() && !params.iter().any(|i| *i == "produces") {
+        let mimes = responses.media_types.iter().map(|(mime, _)| mime);
+        params.push(Ident::new("produces", proc_macro2::Span::call_site()));
+        values.push(quote!({
+            let mut tmp = std::collections::BTreeSet::new();
+            #(
+                tmp.insert(paperclip::v2::models::MediaRange(#mimes.parse().unwrap()));
+            )*
+            Some(tmp)
+        }));
+    }
+    // Streams document the schema of their items, sent as server-sent events by default.
+    if is_stream {
+        let span = proc_macro2::Span::call_site();
+        extensions.push((
+            LitStr::new("x-streaming", span),
+            Lit::Bool(syn::LitBool::new(true, span)),
+        ));
+        if !params.iter().any(|i| *i == "produces") {
+            params.push(Ident::new("produces", span));
+            values.push(quote!({
+                let mut tmp = std::collections::BTreeSet::new();
+                tmp.insert(paperclip::v2::models::MediaRange(
+                    "text/event-stream".parse().unwrap(),
+                ));
+                Some(tmp)
+            }));
+        }
+    }
+    if !extensions.is_empty() {
+        let (keys, ext_values): (Vec<_>, Vec<_>) = extensions.into_iter().unzip();
+        params.push(Ident::new("extensions", proc_macro2::Span::call_site()));
+        values.push(quote!({
+            let mut map = std::collections::BTreeMap::new();
+            #(
+                map.insert(#keys.into(), serde_json::json!(#ext_values));
+            )*
+            map
+        }));
+    }
+    (params, values, responses, callbacks, deprecated_if)
+}
+
+/// Parses a callback, e.g. `callback(name = "onEvent", path = "{$request.body#/url}", handler = "on_event")`,
+/// where the handler is another `api_v2_operation` describing the request made by the API.
+fn parse_callback(
+    ident: &Ident,
+    nested: &Punctuated<NestedMeta, Token![,]>,
+) -> Option<OperationCallback> {
+    let mut name = None;
+    let mut path = None;
+    let mut method = Ident::new("Post", ident.span());
+    let mut handler = None;
+    for meta in nested {
+        match meta {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path: key,
+                lit: Lit::Str(lit),
+                ..
+            })) if key.get_ident().is_some() => {
+                match key.get_ident().unwrap().to_string().as_str() {
+                    "name" => name = Some(lit.value()),
+                    "path" => path = Some(lit.value()),
+                    "method" => match lit.value().to_lowercase().as_str() {
+                        m @ ("get" | "put" | "post" | "delete" | "options" | "head" | "patch") => {
+                            method = Ident::new(&m.to_upper_camel_case(), lit.span())
+                        }
+                        m => emit_error!(lit.span(), "Unsupported callback method {}", m),
+                    },
+                    "handler" => match lit.parse::<Path>() {
+                        // The operation is implemented by the unit struct generated for the handler.
+                        Ok(mut handler_path) => {
+                            if let Some(last) = handler_path.segments.last_mut() {
+                                last.ident = Ident::new(
+                                    &format!("paperclip_{}", last.ident),
+                                    last.ident.span(),
+                                );
+                            }
+                            handler = Some(handler_path)
+                        }
+                        Err(err) => emit_error!(
+                            lit.span(),
+                            "Value {} does not parse as path: {}",
+                            lit.value(),
+                            err
+                        ),
+                    },
+                    x => emit_error!(key.span(), "Unknown callback attribute {}", x),
+                }
+            }
+            _ => emit_error!(
+                meta.span(),
+                "Expected callback attributes mapped to string literals, e.g. callback(name = \"onEvent\", path = \"...\", handler = \"on_event\"): {:?}",
+                meta
+            ),
+        }
+    }
+
+    match (name, path, handler) {
+        (Some(name), Some(path), Some(handler)) => Some(OperationCallback {
+            name,
+            path,
+            method,
+            handler,
+        }),
+        _ => {
+            emit_error!(
+                ident.span(),
+                "Expected name, path and handler in callback(...)"
+            );
+            None
+        }
+    }
+}
+
+/// Parses a response link, e.g. `link(201, name = "GetPet", operation_id = "getPet", parameters(id = "$response.body#/id"))`.
+fn parse_link(ident: &Ident, nested: &Punctuated<NestedMeta, Token![,]>) -> Option<ResponseLink> {
+    let mut code = None;
+    let mut name = None;
+    let mut operation_id = None;
+    let mut parameters = Vec::new();
+    let mut description = None;
+    for meta in nested {
+        match meta {
+            NestedMeta::Lit(Lit::Int(lit)) if code.is_none() => match lit.base10_parse::<u16>() {
+                Ok(c) => code = Some(c),
+                Err(_) => emit_error!(lit.span(), "Invalid u16 in status code"),
+            },
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit),
+                ..
+            })) if path.get_ident().is_some() => {
+                match path.get_ident().unwrap().to_string().as_str() {
+                    "name" => name = Some(lit.value()),
+                    "operation_id" => operation_id = Some(lit.value()),
+                    "description" => description = Some(lit.value()),
+                    x => emit_error!(path.span(), "Unknown link attribute {}", x),
+                }
+            }
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("parameters") => {
+                for param in &list.nested {
+                    match param {
+                        NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                            path,
+                            lit: Lit::Str(lit),
+                            ..
+                        })) if path.get_ident().is_some() => parameters
+                            .push((path.get_ident().unwrap().to_string(), lit.value())),
+                        _ => emit_error!(
+                            param.span(),
+                            "Expected parameter names mapped to string literals, e.g. id = \"$response.body#/id\": {:?}",
+                            param
+                        ),
+                    }
+                }
+            }
+            _ => emit_error!(
+                meta.span(),
+                "Expected link attributes, e.g. link(201, name = \"GetPet\", operation_id = \"getPet\", parameters(id = \"...\")): {:?}",
+                meta
+            ),
+        }
+    }
+
+    match (name, operation_id) {
+        (Some(name), Some(operation_id)) => Some(ResponseLink {
+            code,
+            name,
+            operation_id,
+            parameters,
+            description,
+        }),
+        _ => {
+            emit_error!(ident.span(), "Expected name and operation_id in link(...)");
+            None
+        }
+    }
+}
+
+/// Callback declared in the operation macro attributes.
+struct OperationCallback {
+    name: String,
+    /// URL expression of the request made by the API.
+    path: String,
+    /// Variant of `HttpMethod` used for the request.
+    method: Ident,
+    /// Path to the unit struct of the handler describing the request.
+    handler: Path,
+}
+
+/// Responses declared in the operation macro attributes.
+#[derive(Default)]
+struct OperationResponses {
+    /// Success responses as (status code, body type) pairs.
+    typed: Vec<(u16, Type)>,
+    /// Documentation-only responses as (status code, description) pairs.
+    described: Vec<(u16, String)>,
+    /// Named examples of the success response as (name, value) pairs.
+    examples: Vec<(String, String)>,
+    /// Links from the responses to other operations.
+    links: Vec<ResponseLink>,
+    /// Description overriding the default ("OK") of the 200 response.
+    success_description: Option<String>,
+    /// Schemas of the 200 response per media type as (media type, body type) pairs.
+    media_types: Vec<(String, Type)>,
+    /// Headers of the success response as (header name, description) pairs.
+    headers: Vec<(String, String)>,
+    /// Types of the success response headers as (header name, type) pairs, defaulting to strings.
+    header_types: Vec<(LitStr, Type)>,
+}
+
+/// Returns the media type for the shorthands used in `produces(json = "Foo", xml = "Bar")`.
+fn media_type_shorthand(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "yaml" => "application/yaml",
+        "csv" => "text/csv",
+        "html" => "text/html",
+        "text" => "text/plain",
+        _ => return None,
+    })
+}
+
+/// Parses the body type of the response for the given media type.
+fn push_media_type(responses: &mut OperationResponses, mime: String, ty: &LitStr) {
+    if let Err(err) = mime.parse::<mime::Mime>() {
+        emit_error!(
+            ty.span(),
+            "Value {} does not parse as mime type: {}",
+            mime,
+            err
+        );
+        return;
+    }
+    match ty.parse::<Type>() {
+        Ok(parsed) => responses.media_types.push((mime, parsed)),
+        Err(err) => emit_error!(
+            ty.span(),
+            "Value {} does not parse as type: {}",
+            ty.value(),
+            err
+        ),
+    }
+}
+
+/// Link declared in the operation macro attributes.
+struct ResponseLink {
+    /// Status code of the linked response (the success response by default).
+    code: Option<u16>,
+    name: String,
+    operation_id: String,
+    /// Parameters of the linked operation mapped to their values (runtime expressions).
+    parameters: Vec<(String, String)>,
+    description: Option<String>,
+}
+
+/// Macro attributes which (unlike `NestedMeta`) may also contain lists of `literal = literal`
+/// pairs, e.g., `responses(200 = "Foo")` or `extension("x-foo" = "bar")`.
+#[derive(Default)]
+struct MacroAttrs(Punctuated<MacroAttr, Token![,]>);
+
+impl Parse for MacroAttrs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(MacroAttrs(input.call(Punctuated::parse_terminated)?))
+    }
+}
+
+/// Single macro attribute.
+enum MacroAttr {
+    Pairs(Ident, Vec<(Lit, Lit)>),
+    Meta(NestedMeta),
+}
+
+impl Parse for MacroAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fork = input.fork();
+        let is_pairs = fork.parse::<Ident>().is_ok()
+            && (|| -> syn::Result<bool> {
+                let content;
+                syn::parenthesized!(content in fork);
+                Ok(content.peek(Lit) && content.peek2(Token![=]))
+            })()
+            .unwrap_or(false);
+        if !is_pairs {
+            return Ok(MacroAttr::Meta(input.parse()?));
+        }
+
+        let ident = input.parse::<Ident>()?;
+        let content;
+        syn::parenthesized!(content in input);
+        let mut list = Vec::new();
+        while !content.is_empty() {
+            let key = content.parse::<Lit>()?;
+            content.parse::<Token![=]>()?;
+            list.push((key, content.parse::<Lit>()?));
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+        }
+
+        Ok(MacroAttr::Pairs(ident, list))
+    }
 }
 
 /// Extracts summary from top line doc comment and description from the rest
@@ -387,7 +1298,7 @@ fn extract_fn_documentation(
     Option<proc_macro2::TokenStream>,
     Option<proc_macro2::TokenStream>,
 ) {
-    let docs = extract_documentation(&item_ast.attrs);
+    let (docs, _) = extract_code_samples(&extract_documentation(&item_ast.attrs));
     let lines = docs.lines();
     let mut before_empty = true;
     let (summary, description): (Vec<_>, Vec<_>) = lines.partition(|line| {
@@ -408,25 +1319,76 @@ fn extract_fn_documentation(
     (summary, description)
 }
 
+/// Splits fenced code blocks annotated with a language (e.g. "```bash") out of the given docs.
+/// Returns the remaining docs along with the (language, source) pairs of these blocks.
+fn extract_code_samples(docs: &str) -> (String, Vec<(String, String)>) {
+    let mut prose = vec![];
+    let mut samples = vec![];
+    let mut current: Option<(String, Vec<&str>)> = None;
+    // Blank line following a removed block would otherwise double the paragraph break.
+    let mut skip_blank = false;
+    for line in docs.lines() {
+        let trimmed = line.trim();
+        if std::mem::take(&mut skip_blank) && trimmed.is_empty() {
+            continue;
+        }
+
+        match current.take() {
+            Some((lang, source)) if trimmed == "```" => {
+                samples.push((lang, source.join("\n")));
+                skip_blank = true;
+            }
+            Some((lang, mut source)) => {
+                source.push(line.strip_prefix(' ').unwrap_or(line));
+                current = Some((lang, source));
+            }
+            None => match trimmed.strip_prefix("```").map(str::trim) {
+                Some(lang) if !lang.is_empty() => current = Some((lang.to_owned(), vec![])),
+                _ => prose.push(line),
+            },
+        }
+    }
+
+    // Unterminated block runs until the end of docs.
+    if let Some((lang, source)) = current {
+        samples.push((lang, source.join("\n")));
+    }
+
+    (prose.join("\n"), samples)
+}
+
+/// Code, description, schema and content type of an error response, along with the
+/// attribute declaring it (for spanning error messages).
+type ErrorAttrs<'a> = (
+    Option<u16>,
+    Option<String>,
+    Option<syn::Ident>,
+    Option<String>,
+    &'a NestedMeta,
+);
+
 /// Actual parser and emitter for `api_v2_errors` macro.
 pub fn emit_v2_errors(attrs: TokenStream, input: TokenStream) -> TokenStream {
-    let item_ast = match crate::expect_struct_or_enum(input) {
+    let mut item_ast = match crate::expect_struct_or_enum(input) {
         Ok(i) => i,
         Err(ts) => return ts,
     };
 
+    let mut attrs = crate::parse_input_attrs(attrs);
+    // Codes can also be placed next to the enum variants they're returned for.
+    let (variant_attrs, variant_codes) = take_variant_error_attrs(&mut item_ast);
+    attrs.0.extend(variant_attrs);
     let name = &item_ast.ident;
-    let attrs = crate::parse_input_attrs(attrs);
     let generics = item_ast.generics.clone();
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let mut default_schema: Option<syn::Ident> = None;
-    // Convert macro attributes to tuples in form of (u16, &str, &Option<syn::Ident>)
+    // Convert macro attributes to tuples in form of (u16, &str, &Option<syn::Ident>, &Option<String>)
     let error_codes = attrs
         .0
         .iter()
         // Pair code attrs with description attrs; save attr itself to properly span error messages at later stage
-        .fold(Vec::new(), |mut list: Vec<(Option<u16>, Option<String>, Option<syn::Ident>, _)>, attr| {
+        .fold(Vec::new(), |mut list: Vec<ErrorAttrs<'_>>, attr| {
             let span = attr.span().unwrap();
             match attr {
                 // Read named attribute.
@@ -438,7 +1400,7 @@ pub fn emit_v2_errors(attrs: TokenStream, input: TokenStream) -> TokenStream {
                         (Some("code"), Lit::Int(attr_value)) => {
                             let status_code = attr_value.base10_parse::<u16>()
                                 .map_err(|_| emit_error!(span, "Invalid u16 in code argument")).ok();
-                            list.push((status_code, None, None, attr));
+                            list.push((status_code, None, None, None, attr));
                         }
                         // "description" attribute updates last element in list
                         (Some("description"), Lit::Str(attr_value)) =>
@@ -463,21 +1425,34 @@ pub fn emit_v2_errors(attrs: TokenStream, input: TokenStream) -> TokenStream {
                             } else {
                                 emit_error!(span, "Attribute 'schema' can be only placed after prior 'code' argument");
                             },
+                        // "content" attribute updates last element in list
+                        (Some("content"), Lit::Str(attr_value)) =>
+                            if let Some(last_value) = list.last_mut() {
+                                if last_value.3.is_some() {
+                                    emit_warning!(span, "This attribute overwrites previous content");
+                                }
+                                match attr_value.value().parse::<mime::Mime>() {
+                                    Ok(_) => last_value.3 = Some(attr_value.value()),
+                                    Err(error) => emit_error!(span, "Error parsing content: {}", error),
+                                }
+                            } else {
+                                emit_error!(span, "Attribute 'content' can be only placed after prior 'code' argument");
+                            },
                         (Some("default_schema"), Lit::Str(attr_value)) =>
                             match attr_value.parse() {
                                 Ok(value) => default_schema = Some(value),
                                 Err(error) => emit_error!(span, "Error parsing default_schema: {}", error),
                             },
-                        _ => emit_error!(span, "Invalid macro attribute. Should be plain u16, 'code = u16', 'description = str', 'schema = str' or 'default_schema = str'")
+                        _ => emit_error!(span, "Invalid macro attribute. Should be plain u16, 'code = u16', 'description = str', 'schema = str', 'content = str' or 'default_schema = str'")
                     }
                 }
                 // Read plain status code as attribute.
                 NestedMeta::Lit(Lit::Int(attr_value)) => {
                     let status_code = attr_value.base10_parse::<u16>()
                         .map_err(|_| emit_error!(span, "Invalid u16 in code argument")).ok();
-                    list.push((status_code, None, None, attr));
+                    list.push((status_code, None, None, None, attr));
                 }
-                _ => emit_error!(span, "This macro supports only named attributes - 'code' (u16), 'description' (str), 'schema' (str) or 'default_schema' (str)")
+                _ => emit_error!(span, "This macro supports only named attributes - 'code' (u16), 'description' (str), 'schema' (str), 'content' (str) or 'default_schema' (str)")
             }
 
             list
@@ -485,11 +1460,11 @@ pub fn emit_v2_errors(attrs: TokenStream, input: TokenStream) -> TokenStream {
         .iter()
         // Map code-message pairs into bits of code, filter empty codes out
         .filter_map(|quad| {
-            let (code, description, schema) = match quad {
-                (Some(code), Some(description), schema, _) => {
-                    (code, description.to_owned(), schema.to_owned())
+            let (code, description, schema, content) = match quad {
+                (Some(code), Some(description), schema, content, _) => {
+                    (code, description.to_owned(), schema.to_owned(), content.to_owned())
                 }
-                (Some(code), None, schema, attr) => {
+                (Some(code), None, schema, content, attr) => {
                     let span = attr.span().unwrap();
                     let description = StatusCode::from_u16(*code)
                         .map_err(|_| {
@@ -504,13 +1479,13 @@ pub fn emit_v2_errors(attrs: TokenStream, input: TokenStream) -> TokenStream {
                             })
                         )
                         .unwrap_or_else(|_| String::new());
-                    (code, description, schema.to_owned())
+                    (code, description, schema.to_owned(), content.to_owned())
                 }
-                (None, _, _, _) => return None,
+                (None, _, _, _, _) => return None,
             };
-            Some((*code, description, schema))
+            Some((*code, description, schema, content))
         })
-        .collect::<Vec<(u16, String, Option<syn::Ident>)>>();
+        .collect::<Vec<(u16, String, Option<syn::Ident>, Option<String>)>>();
 
     let error_definitions = error_codes.iter().fold(
         if default_schema.is_none() {
@@ -520,7 +1495,7 @@ pub fn emit_v2_errors(attrs: TokenStream, input: TokenStream) -> TokenStream {
                 #default_schema::update_definitions(map);
             }
         },
-        |mut stream, (_, _, schema)| {
+        |mut stream, (_, _, schema, _)| {
             if let Some(schema) = schema {
                 let tokens = quote! {
                     #schema::update_definitions(map);
@@ -541,7 +1516,7 @@ pub fn emit_v2_errors(attrs: TokenStream, input: TokenStream) -> TokenStream {
     // for compatibility with previous error trait
     let error_map = error_codes.iter().fold(
         proc_macro2::TokenStream::new(),
-        |mut stream, (code, description, _)| {
+        |mut stream, (code, description, _, _)| {
             let token = quote! {
                 (#code, #description),
             };
@@ -551,41 +1526,45 @@ pub fn emit_v2_errors(attrs: TokenStream, input: TokenStream) -> TokenStream {
     );
 
     let update_error_helper = quote! {
-        fn update_error_definitions(code: &u16, description: &str, schema: &Option<&str>, op: &mut paperclip::v2::models::DefaultOperationRaw) {
+        fn update_error_definitions(code: &u16, description: &str, schema: &Option<&str>, content: &Option<&str>, op: &mut paperclip::v2::models::DefaultOperationRaw) {
+            let mut response = paperclip::v2::models::DefaultResponseRaw {
+                description: Some(description.to_string()),
+                ..Default::default()
+            };
             if let Some(schema) = &schema {
-                op.responses.insert(code.to_string(), paperclip::v2::models::Either::Right(paperclip::v2::models::Response {
-                    description: Some(description.to_string()),
-                    schema: Some(paperclip::v2::models::DefaultSchemaRaw {
-                        name: Some(schema.to_string()),
-                        reference: Some(format!("#/definitions/{}", schema)),
-                        .. Default::default()
-                    }),
-                    ..Default::default()
-                }));
-            } else {
-                op.responses.insert(code.to_string(), paperclip::v2::models::Either::Right(paperclip::v2::models::DefaultResponseRaw {
-                    description: Some(description.to_string()),
-                    ..Default::default()
-                }));
+                response.schema = Some(paperclip::v2::models::DefaultSchemaRaw {
+                    name: Some(schema.to_string()),
+                    reference: Some(format!("#/definitions/{}", schema)),
+                    .. Default::default()
+                });
             }
+            if let Some(content) = &content {
+                let mime = content.parse().expect("parsing error content type");
+                response.produces = Some(std::iter::once(paperclip::v2::models::MediaRange(mime)).collect());
+            }
+            op.responses.insert(code.to_string(), paperclip::v2::models::Either::Right(response));
         }
     };
     let default_schema = default_schema.map(|i| i.to_string());
     let update_errors = error_codes.iter().fold(
         update_error_helper,
-        |mut stream, (code, description, schema)| {
+        |mut stream, (code, description, schema, content)| {
+            let content = match content {
+                Some(content) => quote!(Some(#content)),
+                None => quote!(None),
+            };
             let tokens = if let Some(schema) = schema {
                 let schema = schema.to_string();
                 quote! {
-                    update_error_definitions(&#code, #description, &Some(#schema), op);
+                    update_error_definitions(&#code, #description, &Some(#schema), &#content, op);
                 }
             } else if let Some(scheme) = &default_schema {
                 quote! {
-                    update_error_definitions(&#code, #description, &Some(#scheme), op);
+                    update_error_definitions(&#code, #description, &Some(#scheme), &#content, op);
                 }
             } else {
                 quote! {
-                    update_error_definitions(&#code, #description, &None, op);
+                    update_error_definitions(&#code, #description, &None, &#content, op);
                 }
             };
             stream.extend(tokens);
@@ -593,6 +1572,28 @@ pub fn emit_v2_errors(attrs: TokenStream, input: TokenStream) -> TokenStream {
         },
     );
 
+    let error_code = if variant_codes.is_empty() {
+        quote!()
+    } else {
+        let arms = variant_codes.iter().map(|(variant, code)| {
+            let description = error_codes
+                .iter()
+                .find(|(c, _, _, _)| c == code)
+                .map(|(_, description, _, _)| description.clone())
+                .unwrap_or_default();
+            quote!(Self::#variant { .. } => Some((#code, #description)),)
+        });
+        quote! {
+            fn error_code(&self) -> Option<(u16, &'static str)> {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#arms)*
+                    _ => None,
+                }
+            }
+        }
+    };
+
     let gen = quote! {
         #item_ast
 
@@ -604,6 +1605,91 @@ pub fn emit_v2_errors(attrs: TokenStream, input: TokenStream) -> TokenStream {
                 #update_errors
             }
             #update_definitions
+            #error_code
+        }
+    };
+
+    gen.into()
+}
+
+/// Takes the `#[api_v2_errors_variant(...)]` attributes off the variants of the given enum
+/// and returns their arguments (which are the same as the ones of `#[api_v2_errors]`),
+/// along with the status code of each marked variant.
+fn take_variant_error_attrs(item_ast: &mut DeriveInput) -> (Vec<NestedMeta>, Vec<(Ident, u16)>) {
+    let variants = match &mut item_ast.data {
+        Data::Enum(e) => &mut e.variants,
+        _ => return (vec![], vec![]),
+    };
+
+    let mut nested = vec![];
+    let mut codes = vec![];
+    for variant in variants.iter_mut() {
+        let ident = variant.ident.clone();
+        variant.attrs.retain(|attr| {
+            if !attr.path.is_ident("api_v2_errors_variant") {
+                return true;
+            }
+
+            match attr.parse_meta() {
+                Ok(Meta::List(list)) => {
+                    // Every variant starts its own code, so that its description or schema
+                    // doesn't end up in the code of the previous variant.
+                    let code = match list.nested.first() {
+                        Some(NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                            path,
+                            lit: Lit::Int(code),
+                            ..
+                        }))) if path.is_ident("code") => Some(code),
+                        Some(NestedMeta::Lit(Lit::Int(code))) => Some(code),
+                        _ => None,
+                    };
+                    match code.and_then(|c| c.base10_parse::<u16>().ok()) {
+                        Some(code) => codes.push((ident.clone(), code)),
+                        None => emit_error!(
+                            attr.span().unwrap(),
+                            "Expected the status code as the first argument, e.g. `#[api_v2_errors_variant(code = 404)]`"
+                        ),
+                    }
+                    nested.extend(list.nested);
+                }
+                _ => emit_error!(
+                    attr.span().unwrap(),
+                    "Expected arguments, e.g. `#[api_v2_errors_variant(code = 404)]`"
+                ),
+            }
+            false
+        });
+    }
+
+    (nested, codes)
+}
+
+/// Actual parser and emitter for `Apiv2ResponseError` derive macro.
+pub fn emit_v2_response_error(input: TokenStream) -> TokenStream {
+    let item_ast = match crate::expect_struct_or_enum(input) {
+        Ok(i) => i,
+        Err(ts) => return ts,
+    };
+
+    let name = &item_ast.ident;
+    let (impl_generics, ty_generics, where_clause) = item_ast.generics.split_for_impl();
+
+    let gen = quote! {
+        impl #impl_generics std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match paperclip::v2::schema::Apiv2Errors::error_code(self) {
+                    Some((_, description)) => f.write_str(description),
+                    None => f.write_str("Internal Server Error"),
+                }
+            }
+        }
+
+        impl #impl_generics actix_web::error::ResponseError for #name #ty_generics #where_clause {
+            fn status_code(&self) -> actix_web::http::StatusCode {
+                paperclip::v2::schema::Apiv2Errors::error_code(self)
+                    .and_then(|(code, _)| actix_web::http::StatusCode::from_u16(code).ok())
+                    .unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)
+            }
         }
     };
 
@@ -690,42 +1776,339 @@ pub fn emit_v2_errors_overlay(attrs: TokenStream, input: TokenStream) -> TokenSt
             }
         }
 
-        impl actix_web::error::ResponseError for #name {
-            fn status_code(&self) -> actix_web::http::StatusCode {
-                self.0.status_code()
-            }
-            fn error_response(&self) -> actix_web::HttpResponse {
-                self.0.error_response()
+        impl actix_web::error::ResponseError for #name {
+            fn status_code(&self) -> actix_web::http::StatusCode {
+                self.0.status_code()
+            }
+            fn error_response(&self) -> actix_web::HttpResponse {
+                self.0.error_response()
+            }
+        }
+
+        impl #impl_generics paperclip::v2::schema::Apiv2Errors for #name #ty_generics #where_clause {
+            const ERROR_MAP: &'static [(u16, &'static str)] = &[];
+            fn update_definitions(map: &mut std::collections::BTreeMap<String, paperclip::v2::models::DefaultSchemaRaw>) {
+                #inner::update_definitions(map);
+            }
+            fn update_error_definitions(op: &mut paperclip::v2::models::DefaultOperationRaw) {
+                #inner::update_error_definitions(op);
+                #filter_error_codes
+            }
+        }
+    };
+
+    gen.into()
+}
+
+fn extract_rename(attrs: &[Attribute]) -> Option<String> {
+    let attrs = extract_openapi_attrs(attrs);
+    for attr in attrs.flat_map(|attr| attr.into_iter()) {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
+            if nv.path.is_ident("rename") {
+                if let Lit::Str(s) = nv.lit {
+                    return Some(s.value());
+                } else {
+                    emit_error!(
+                        nv.lit.span().unwrap(),
+                        format!(
+                            "`#[{}(rename = \"...\")]` expects a string argument",
+                            SCHEMA_MACRO_ATTR
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn extract_example(attrs: &[Attribute]) -> Option<String> {
+    let attrs = extract_openapi_attrs(attrs);
+    for attr in attrs.flat_map(|attr| attr.into_iter()) {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
+            if nv.path.is_ident("example") {
+                if let Lit::Str(s) = nv.lit {
+                    return Some(s.value());
+                } else {
+                    emit_error!(
+                        nv.lit.span().unwrap(),
+                        format!(
+                            "`#[{}(example = \"...\")]` expects a string argument",
+                            SCHEMA_MACRO_ATTR
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn extract_description(attrs: &[Attribute]) -> Option<String> {
+    let attrs = extract_openapi_attrs(attrs);
+    for attr in attrs.flat_map(|attr| attr.into_iter()) {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
+            if nv.path.is_ident("description") {
+                if let Lit::Str(s) = nv.lit {
+                    return Some(s.value());
+                } else {
+                    emit_error!(
+                        nv.lit.span().unwrap(),
+                        format!(
+                            "`#[{}(description = \"...\")]` expects a string argument",
+                            SCHEMA_MACRO_ATTR
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Extracts the deprecation of a field, i.e. `#[openapi(deprecated)]` or
+/// `#[openapi(deprecated = "...")]` along with a note on what to use instead.
+fn extract_deprecated_note(attrs: &[Attribute]) -> Option<Option<String>> {
+    let attrs = extract_openapi_attrs(attrs);
+    for attr in attrs.flat_map(|attr| attr.into_iter()) {
+        match attr {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("deprecated") => return Some(None),
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("deprecated") => {
+                if let Lit::Str(s) = nv.lit {
+                    return Some(Some(s.value()));
+                } else {
+                    emit_error!(
+                        nv.lit.span().unwrap(),
+                        format!(
+                            "`#[{}(deprecated = \"...\")]` expects a string argument",
+                            SCHEMA_MACRO_ATTR
+                        ),
+                    );
+                }
+            }
+            _ => (),
+        }
+    }
+
+    None
+}
+
+fn extract_priority(attrs: &[Attribute]) -> Option<i64> {
+    let attrs = extract_openapi_attrs(attrs);
+    for attr in attrs.flat_map(|attr| attr.into_iter()) {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
+            if nv.path.is_ident("priority") {
+                match &nv.lit {
+                    Lit::Int(i) if i.base10_parse::<i64>().is_ok() => {
+                        return i.base10_parse::<i64>().ok()
+                    }
+                    _ => emit_error!(
+                        nv.lit.span().unwrap(),
+                        format!(
+                            "`#[{}(priority = N)]` expects an integer argument",
+                            SCHEMA_MACRO_ATTR
+                        ),
+                    ),
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn extract_pattern(attrs: &[Attribute]) -> Option<String> {
+    let attrs = extract_openapi_attrs(attrs);
+    for attr in attrs.flat_map(|attr| attr.into_iter()) {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
+            if nv.path.is_ident("pattern") {
+                if let Lit::Str(s) = nv.lit {
+                    return Some(s.value());
+                } else {
+                    emit_error!(
+                        nv.lit.span().unwrap(),
+                        format!(
+                            "`#[{}(pattern = \"...\")]` expects a string argument",
+                            SCHEMA_MACRO_ATTR
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    None
+}
+
+fn extract_format(attrs: &[Attribute]) -> Option<String> {
+    let attrs = extract_openapi_attrs(attrs);
+    for attr in attrs.flat_map(|attr| attr.into_iter()) {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
+            if nv.path.is_ident("format") {
+                if let Lit::Str(s) = nv.lit {
+                    return Some(s.value());
+                } else {
+                    emit_error!(
+                        nv.lit.span().unwrap(),
+                        format!(
+                            "`#[{}(format = \"...\")]` expects a string argument",
+                            SCHEMA_MACRO_ATTR
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Extracts the serialization style of the (query) parameter generated from a field,
+/// i.e. `#[openapi(style = "deepObject")]`.
+fn extract_style(attrs: &[Attribute]) -> Option<proc_macro2::TokenStream> {
+    let attrs = extract_openapi_attrs(attrs);
+    for attr in attrs.flat_map(|attr| attr.into_iter()) {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
+            if nv.path.is_ident("style") {
+                let style = match &nv.lit {
+                    Lit::Str(s) => match s.value().as_str() {
+                        "form" => Some(quote!(Form)),
+                        "spaceDelimited" => Some(quote!(SpaceDelimited)),
+                        "pipeDelimited" => Some(quote!(PipeDelimited)),
+                        "deepObject" => Some(quote!(DeepObject)),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                match style {
+                    Some(style) => {
+                        return Some(quote!(paperclip::v2::models::ParameterStyle::#style))
+                    }
+                    None => emit_error!(
+                        nv.lit.span().unwrap(),
+                        format!(
+                            "`#[{}(style = \"...\")]` expects one of \"form\", \"spaceDelimited\", \"pipeDelimited\" or \"deepObject\"",
+                            SCHEMA_MACRO_ATTR
+                        ),
+                    ),
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Extracts the name of a shared example, i.e. `#[openapi(example_ref = "...")]`.
+fn extract_example_ref(attrs: &[Attribute]) -> Option<String> {
+    let attrs = extract_openapi_attrs(attrs);
+    for attr in attrs.flat_map(|attr| attr.into_iter()) {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
+            if nv.path.is_ident("example_ref") {
+                if let Lit::Str(s) = nv.lit {
+                    return Some(s.value());
+                } else {
+                    emit_error!(
+                        nv.lit.span().unwrap(),
+                        format!(
+                            "`#[{}(example_ref = \"...\")]` expects a string argument",
+                            SCHEMA_MACRO_ATTR
+                        ),
+                    );
+                }
             }
         }
+    }
 
-        impl #impl_generics paperclip::v2::schema::Apiv2Errors for #name #ty_generics #where_clause {
-            const ERROR_MAP: &'static [(u16, &'static str)] = &[];
-            fn update_definitions(map: &mut std::collections::BTreeMap<String, paperclip::v2::models::DefaultSchemaRaw>) {
-                #inner::update_definitions(map);
+    None
+}
+
+/// Extracts the XML metadata of a schema, i.e.
+/// `#[openapi(xml(name = "...", namespace = "...", prefix = "...", attribute, wrapped))]`.
+fn extract_xml(attrs: &[Attribute]) -> Option<proc_macro2::TokenStream> {
+    let attrs = extract_openapi_attrs(attrs);
+    for attr in attrs.flat_map(|attr| attr.into_iter()) {
+        if let NestedMeta::Meta(Meta::List(list)) = attr {
+            if !list.path.is_ident("xml") {
+                continue;
             }
-            fn update_error_definitions(op: &mut paperclip::v2::models::DefaultOperationRaw) {
-                #inner::update_error_definitions(op);
-                #filter_error_codes
+
+            let (mut name, mut namespace, mut prefix) = (None, None, None);
+            let (mut attribute, mut wrapped) = (false, false);
+            for meta in list.nested {
+                match meta {
+                    NestedMeta::Meta(Meta::NameValue(nv)) => {
+                        let value = match &nv.lit {
+                            Lit::Str(s) => s.value(),
+                            _ => {
+                                emit_error!(
+                                    nv.lit.span().unwrap(),
+                                    format!(
+                                        "`#[{}(xml(...))]` expects string values",
+                                        SCHEMA_MACRO_ATTR
+                                    ),
+                                );
+                                continue;
+                            }
+                        };
+                        if nv.path.is_ident("name") {
+                            name = Some(value);
+                        } else if nv.path.is_ident("namespace") {
+                            namespace = Some(value);
+                        } else if nv.path.is_ident("prefix") {
+                            prefix = Some(value);
+                        } else {
+                            emit_error!(
+                                nv.path.span().unwrap(),
+                                "expected one of `name`, `namespace` or `prefix`"
+                            );
+                        }
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("attribute") => {
+                        attribute = true
+                    }
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("wrapped") => {
+                        wrapped = true
+                    }
+                    _ => emit_error!(
+                        meta.span().unwrap(),
+                        "expected one of `name`, `namespace`, `prefix`, `attribute` or `wrapped`"
+                    ),
+                }
             }
+
+            let name = quote_option(name.as_ref());
+            let namespace = quote_option(namespace.as_ref());
+            let prefix = quote_option(prefix.as_ref());
+            return Some(quote!(paperclip::v2::models::Xml {
+                name: #name,
+                namespace: #namespace,
+                prefix: #prefix,
+                attribute: #attribute,
+                wrapped: #wrapped,
+            }));
         }
-    };
+    }
 
-    gen.into()
+    None
 }
 
-fn extract_rename(attrs: &[Attribute]) -> Option<String> {
+/// Extracts the constant value of a unit struct, i.e. `#[openapi(const_value = "...")]`.
+fn extract_const_value(attrs: &[Attribute]) -> Option<String> {
     let attrs = extract_openapi_attrs(attrs);
     for attr in attrs.flat_map(|attr| attr.into_iter()) {
         if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
-            if nv.path.is_ident("rename") {
+            if nv.path.is_ident("const_value") {
                 if let Lit::Str(s) = nv.lit {
                     return Some(s.value());
                 } else {
                     emit_error!(
                         nv.lit.span().unwrap(),
                         format!(
-                            "`#[{}(rename = \"...\")]` expects a string argument",
+                            "`#[{}(const_value = \"...\")]` expects a string argument",
                             SCHEMA_MACRO_ATTR
                         ),
                     );
@@ -737,18 +2120,138 @@ fn extract_rename(attrs: &[Attribute]) -> Option<String> {
     None
 }
 
-fn extract_example(attrs: &[Attribute]) -> Option<String> {
+/// Extracts the allowed values of a field, i.e. `#[openapi(enum_values("a", "b"))]`.
+fn extract_enum_values(attrs: &[Attribute]) -> Option<Vec<Lit>> {
+    let attrs = extract_openapi_attrs(attrs);
+    for attr in attrs.flat_map(|attr| attr.into_iter()) {
+        if let NestedMeta::Meta(Meta::List(list)) = attr {
+            if list.path.is_ident("enum_values") {
+                let mut values = Vec::new();
+                for value in list.nested {
+                    if let NestedMeta::Lit(lit) = value {
+                        values.push(lit);
+                    } else {
+                        emit_error!(
+                            value.span().unwrap(),
+                            format!(
+                                "`#[{}(enum_values(...))]` expects literal values",
+                                SCHEMA_MACRO_ATTR
+                            ),
+                        );
+                    }
+                }
+                return Some(values);
+            }
+        }
+    }
+
+    None
+}
+
+/// Extracts the representation of an enum, i.e. `#[openapi(repr = "int")]` or
+/// `#[openapi(repr = "string")]`.
+fn extract_repr(attrs: &[Attribute]) -> Option<String> {
     let attrs = extract_openapi_attrs(attrs);
     for attr in attrs.flat_map(|attr| attr.into_iter()) {
         if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
-            if nv.path.is_ident("example") {
-                if let Lit::Str(s) = nv.lit {
-                    return Some(s.value());
+            if nv.path.is_ident("repr") {
+                match nv.lit {
+                    Lit::Str(s) if s.value() == "int" || s.value() == "string" => {
+                        return Some(s.value())
+                    }
+                    _ => emit_error!(
+                        nv.lit.span().unwrap(),
+                        format!(
+                            "`#[{}(repr = \"...\")]` expects either \"int\" or \"string\"",
+                            SCHEMA_MACRO_ATTR
+                        ),
+                    ),
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Primitive representations of C-like enums.
+const INT_REPRS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize",
+];
+
+/// Checks whether an enum should be documented by its discriminants, i.e. when it's marked
+/// with `#[openapi(repr = "int")]` or serialized with `serde_repr`. Other C-like enums with
+/// an integer `#[repr(...)]` are serialized by variant name (serde's default), so they're
+/// kept as string enums, with a warning pointing to the attribute.
+fn is_int_enum(item_attrs: &[Attribute], e: &DataEnum) -> bool {
+    if let Some(repr) = extract_repr(item_attrs) {
+        return repr == "int";
+    }
+
+    let is_c_like = e
+        .variants
+        .iter()
+        .all(|var| matches!(var.fields, Fields::Unit))
+        && e.variants.iter().any(|var| var.discriminant.is_some());
+    if !is_c_like {
+        return false;
+    }
+
+    let has_literal_discriminants = e
+        .variants
+        .iter()
+        .filter_map(|var| var.discriminant.as_ref())
+        .all(|(_, expr)| discriminant_value(expr).is_some());
+    let derives = item_attrs
+        .iter()
+        .filter(|a| a.path.is_ident("derive"))
+        .filter_map(|a| match a.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|meta| match meta {
+            NestedMeta::Meta(Meta::Path(path)) => path.segments.last().map(|s| s.ident.to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if derives.iter().any(|d| d == "Serialize_repr") && has_literal_discriminants {
+        return true;
+    }
+
+    let int_repr = item_attrs
+        .iter()
+        .filter(|a| a.path.is_ident("repr"))
+        .find(|a| match a.parse_meta() {
+            Ok(Meta::List(list)) => list.nested.iter().any(|meta| match meta {
+                NestedMeta::Meta(Meta::Path(path)) => INT_REPRS.iter().any(|r| path.is_ident(r)),
+                _ => false,
+            }),
+            _ => false,
+        });
+    if let Some(attr) = int_repr {
+        emit_warning!(
+            attr.span().unwrap(),
+            "enum is documented by its variant names, as serde serializes it by default";
+            help = "use `#[{}(repr = \"int\")]` if it's serialized by its discriminants", SCHEMA_MACRO_ATTR
+        );
+    }
+
+    false
+}
+
+fn extract_write_only(attrs: &[Attribute]) -> Option<bool> {
+    let attrs = extract_openapi_attrs(attrs);
+    for attr in attrs.flat_map(|attr| attr.into_iter()) {
+        if let NestedMeta::Meta(Meta::NameValue(nv)) = attr {
+            if nv.path.is_ident("write_only") {
+                if let Lit::Bool(b) = nv.lit {
+                    return Some(b.value);
                 } else {
                     emit_error!(
                         nv.lit.span().unwrap(),
                         format!(
-                            "`#[{}(example = \"...\")]` expects a string argument",
+                            "`#[{}(write_only = ...)]` expects a boolean argument",
                             SCHEMA_MACRO_ATTR
                         ),
                     );
@@ -779,11 +2282,25 @@ pub fn emit_v2_definition(input: TokenStream, for_response: bool) -> TokenStream
         quote!(
             serde_json::from_str::<serde_json::Value>(#example).ok().or_else(|| Some(#example.into()))
         )
+    } else if OpenApiExampleFromDefault::exists(&item_ast.attrs) {
+        quote!(serde_json::to_value(<Self as Default>::default()).ok())
     } else {
         quote!(None)
     };
+    let example_ref = match extract_example_ref(&item_ast.attrs) {
+        Some(name) => quote!(Some(#name.into())),
+        None => quote!(None),
+    };
+    let xml = match extract_xml(&item_ast.attrs) {
+        Some(xml) => quote!(Some(#xml)),
+        None => quote!(None),
+    };
 
     let props = SerdeProps::from_item_attrs(&item_ast.attrs);
+    let extensions = extract_extensions(
+        &item_ast.attrs,
+        &Ident::new("schema", proc_macro2::Span::call_site()),
+    );
 
     let name = &item_ast.ident;
 
@@ -795,6 +2312,18 @@ pub fn emit_v2_definition(input: TokenStream, for_response: bool) -> TokenStream
         param.bounds.push(bound.clone().into());
     });
 
+    let skip_if_empty = OpenApiSkipIfEmpty::exists(&item_ast.attrs);
+    // Whether the schema is skipped for being empty. This is decided here rather than on the
+    // generated schema, so that `name()` doesn't need to build it.
+    let skip_empty_schema = skip_if_empty
+        && match &item_ast.data {
+            Data::Struct(s) => match &s.fields {
+                Fields::Named(f) => f.named.iter().all(|f| SerdeSkip::exists(&f.attrs)),
+                Fields::Unnamed(f) => f.unnamed.is_empty(),
+                Fields::Unit => extract_const_value(&item_ast.attrs).is_none(),
+            },
+            _ => false,
+        };
     let operation_modifier_impl = if for_response {
         add_response_operation_modifier_impl(name, &generics)
     } else {
@@ -804,6 +2333,7 @@ pub fn emit_v2_definition(input: TokenStream, for_response: bool) -> TokenStream
 
     // FIXME: Use attr path segments to find flattening, skipping, etc.
     let mut props_gen = quote! {};
+    let mut empty_schema_warning = quote! {};
 
     match &item_ast.data {
         Data::Struct(ref s) => {
@@ -818,15 +2348,47 @@ pub fn emit_v2_definition(input: TokenStream, for_response: bool) -> TokenStream
                     handle_unnamed_field_struct(f, &item_ast.attrs, &mut props_gen)
                 }
                 Fields::Unit => {
-                    emit_warning!(
-                        s.struct_token.span().unwrap(),
-                        "unit structs do not have any fields and hence will have empty schema.";
-                        help = "{}", &*EMPTY_SCHEMA_HELP;
-                    );
+                    if let Some(value) = extract_const_value(&item_ast.attrs) {
+                        // Constants are single-valued enums, as `const` isn't supported
+                        // in OpenAPI v2 (or v3.0).
+                        props_gen.extend(quote!({
+                            let value = serde_json::from_str::<serde_json::Value>(#value)
+                                .unwrap_or_else(|_| #value.into());
+                            schema.data_type = match &value {
+                                serde_json::Value::Bool(_) => Some(DataType::Boolean),
+                                serde_json::Value::Number(n) if n.is_f64() => Some(DataType::Number),
+                                serde_json::Value::Number(_) => Some(DataType::Integer),
+                                serde_json::Value::String(_) => Some(DataType::String),
+                                _ => None,
+                            };
+                            schema.enum_ = vec![value];
+                        }));
+                    } else if !skip_if_empty {
+                        // `emit_warning!` is dropped on stable, so this is raised as a
+                        // deprecation instead, which can also be denied or allowed.
+                        let note = format!(
+                            "unit structs do not have any fields and hence will have empty schema; {}",
+                            &*EMPTY_SCHEMA_HELP
+                        );
+                        empty_schema_warning = quote_spanned! {s.struct_token.span=>
+                            const _: () = {
+                                #[deprecated(note = #note)]
+                                struct EmptySchema;
+                                let _ = EmptySchema;
+                            };
+                        };
+                    }
                 }
             }
         }
-        Data::Enum(ref e) => handle_enum(e, &props, &mut props_gen),
+        Data::Enum(ref e) if is_int_enum(&item_ast.attrs, e) => handle_int_enum(e, &mut props_gen),
+        Data::Enum(ref e) => handle_enum(
+            e,
+            &props,
+            OpenApiOneOf::exists(&item_ast.attrs),
+            OpenApiCollapsed::exists(&item_ast.attrs),
+            &mut props_gen,
+        ),
         Data::Union(ref u) => emit_error!(
             u.union_token.span().unwrap(),
             "unions are unsupported for deriving schema"
@@ -834,12 +2396,28 @@ pub fn emit_v2_definition(input: TokenStream, for_response: bool) -> TokenStream
     };
 
     let base_name = extract_rename(&item_ast.attrs).unwrap_or_else(|| name.to_string());
-    let type_params: Vec<&Ident> = generics.type_params().map(|p| &p.ident).collect();
-    let schema_name = if type_params.is_empty() {
+    // Both type and const generics are part of the name, so that different
+    // instantiations don't collide in the definitions.
+    let generic_names: Vec<proc_macro2::TokenStream> = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(ty) => {
+                let ident = &ty.ident;
+                Some(quote!(#ident::name()))
+            }
+            GenericParam::Const(c) => {
+                let ident = &c.ident;
+                Some(quote!(Some(#ident.to_string())))
+            }
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect();
+    let schema_name = if generic_names.is_empty() {
         quote! { #base_name }
     } else {
         let type_names = quote! {
-            [#(#type_params::name()),*]
+            [#(#generic_names),*]
                 .iter()
                 .filter_map(|n| n.to_owned())
                 .collect::<Vec<String>>()
@@ -854,6 +2432,8 @@ pub fn emit_v2_definition(input: TokenStream, for_response: bool) -> TokenStream
         let mut schema = DefaultSchemaRaw {
             name: Some(#schema_name.into()),
             example: #example,
+            example_ref: #example_ref,
+            xml: #xml,
             ..Default::default()
         };
     };
@@ -863,6 +2443,8 @@ pub fn emit_v2_definition(input: TokenStream, for_response: bool) -> TokenStream
         let mut schema = DefaultSchemaRaw {
             name: Some(Self::__paperclip_schema_name()), // Add name for later use.
             example: #example,
+            example_ref: #example_ref,
+            xml: #xml,
             .. Default::default()
         };
     };
@@ -887,15 +2469,30 @@ pub fn emit_v2_definition(input: TokenStream, for_response: bool) -> TokenStream
     };
 
     #[cfg(not(feature = "path-in-definition"))]
-    let const_name_def = quote! {
-        fn name() -> Option<String> {
-            Some(#schema_name.to_string())
+    let const_name_def = if skip_empty_schema {
+        // Empty schemas don't have a name (and hence aren't added to the definitions).
+        quote! {
+            fn name() -> Option<String> {
+                None
+            }
+        }
+    } else {
+        quote! {
+            fn name() -> Option<String> {
+                Some(#schema_name.to_string())
+            }
         }
     };
 
     #[cfg(feature = "path-in-definition")]
     let const_name_def = quote!();
 
+    #[cfg(not(feature = "path-in-definition"))]
+    let guard_name = quote!(#schema_name.to_string());
+
+    #[cfg(feature = "path-in-definition")]
+    let guard_name = quote!(Self::__paperclip_schema_name());
+
     #[cfg(not(feature = "path-in-definition"))]
     let props_gen_empty_name_def = quote! {
         schema.name = Some(#schema_name.into());
@@ -906,6 +2503,37 @@ pub fn emit_v2_definition(input: TokenStream, for_response: bool) -> TokenStream
         schema.name = Some(Self::__paperclip_schema_name());
     };
 
+    let raw_schema_def = if skip_empty_schema {
+        // Inlined as `{}` wherever it's used.
+        quote! {
+            fn raw_schema() -> paperclip::v2::models::DefaultSchemaRaw {
+                Default::default()
+            }
+        }
+    } else {
+        quote! {
+            fn raw_schema() -> paperclip::v2::models::DefaultSchemaRaw {
+                use paperclip::v2::models::{DataType, DataTypeFormat, DefaultSchemaRaw};
+                use paperclip::v2::schema::TypedData;
+
+                // Recursive types get a `$ref` to themselves instead of being inlined forever.
+                paperclip::v2::schema::raw_schema_guarded(&#guard_name, || {
+                    #default_schema_raw_def
+
+                    #props_gen
+                    // props_gen may override the schema for unnamed structs with 1 element
+                    // as it replaces the struct type with inner type.
+                    // make sure we set the name properly if props_gen is not empty
+                    if !#props_gen_empty {
+                        #props_gen_empty_name_def
+                    }
+                    #extensions
+                    schema
+                })
+            }
+        }
+    };
+
     let gen = quote! {
         impl #impl_generics #name #ty_generics #where_clause {
             #paperclip_schema_name_def
@@ -917,20 +2545,59 @@ pub fn emit_v2_definition(input: TokenStream, for_response: bool) -> TokenStream
                 #docs
             }
 
-            fn raw_schema() -> paperclip::v2::models::DefaultSchemaRaw {
-                use paperclip::v2::models::{DataType, DataTypeFormat, DefaultSchemaRaw};
-                use paperclip::v2::schema::TypedData;
+            #raw_schema_def
+        }
+
+        #empty_schema_warning
+        #operation_modifier_impl
+    };
 
-                #default_schema_raw_def
+    gen.into()
+}
 
-                #props_gen
-                // props_gen may override the schema for unnamed structs with 1 element
-                // as it replaces the struct type with inner type.
-                // make sure we set the name properly if props_gen is not empty
-                if !#props_gen_empty {
-                    #props_gen_empty_name_def
-                }
-                schema
+/// Actual parser and emitter for `impl_apiv2_schema` macro.
+pub fn emit_v2_schema_impl(input: TokenStream) -> TokenStream {
+    let SchemaImplInput {
+        ty,
+        data_type,
+        format,
+    } = parse_macro_input!(input as SchemaImplInput);
+
+    let data_type = match data_type.to_string().as_str() {
+        "integer" => quote!(Integer),
+        "number" => quote!(Number),
+        "string" => quote!(String),
+        "boolean" => quote!(Boolean),
+        _ => {
+            emit_error!(
+                data_type.span(),
+                "Expected one of the primitive data types: integer, number, string or boolean"
+            );
+            return quote!().into();
+        }
+    };
+
+    let format = match format {
+        Some(lit) => quote_format(&lit.value()),
+        None => quote!(None),
+    };
+
+    #[cfg(not(feature = "nightly"))]
+    let operation_modifier_impl = quote! {
+        impl paperclip::actix::OperationModifier for #ty {}
+    };
+
+    #[cfg(feature = "nightly")]
+    let operation_modifier_impl = quote!();
+
+    let gen = quote! {
+        impl paperclip::v2::schema::TypedData for #ty {
+            fn data_type() -> paperclip::v2::models::DataType {
+                paperclip::v2::models::DataType::#data_type
+            }
+
+            fn format() -> Option<paperclip::v2::models::DataTypeFormat> {
+                #format
             }
         }
 
@@ -940,6 +2607,37 @@ pub fn emit_v2_definition(input: TokenStream, for_response: bool) -> TokenStream
     gen.into()
 }
 
+/// Input of the `impl_apiv2_schema` macro, i.e., `Type => data_type[, format = "format"]`.
+struct SchemaImplInput {
+    ty: Type,
+    data_type: Ident,
+    format: Option<LitStr>,
+}
+
+impl Parse for SchemaImplInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let data_type = input.parse()?;
+        let mut format = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<Ident>()?;
+            if key != "format" {
+                return Err(syn::Error::new(key.span(), "Expected `format`"));
+            }
+            input.parse::<Token![=]>()?;
+            format = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(SchemaImplInput {
+            ty,
+            data_type,
+            format,
+        })
+    }
+}
+
 /// Actual parser and emitter for `Apiv2Security` derive macro.
 pub fn emit_v2_security(input: TokenStream) -> TokenStream {
     let item_ast = match crate::expect_struct_or_enum(input) {
@@ -1179,35 +2877,6 @@ pub fn emit_v2_header(input: TokenStream) -> TokenStream {
         valid_attrs
     );
 
-    fn quote_format(format: &str) -> proc_macro2::TokenStream {
-        match format {
-            "int32" => quote! { Some(paperclip::v2::models::DataTypeFormat::Int32) },
-            "int64" => quote! { Some(paperclip::v2::models::DataTypeFormat::Int64) },
-            "float" => quote! { Some(paperclip::v2::models::DataTypeFormat::Float) },
-            "double" => quote! { Some(paperclip::v2::models::DataTypeFormat::Double) },
-            "byte" => quote! { Some(paperclip::v2::models::DataTypeFormat::Byte) },
-            "binary" => quote! { Some(paperclip::v2::models::DataTypeFormat::Binary) },
-            "date" => quote! { Some(paperclip::v2::models::DataTypeFormat::Date) },
-            "datetime" | "date-time" => {
-                quote! { Some(paperclip::v2::models::DataTypeFormat::DateTime) }
-            }
-            "password" => quote! { Some(paperclip::v2::models::DataTypeFormat::Password) },
-            "url" => quote! { Some(paperclip::v2::models::DataTypeFormat::Url) },
-            "uuid" => quote! { Some(paperclip::v2::models::DataTypeFormat::Uuid) },
-            "ip" => quote! { Some(paperclip::v2::models::DataTypeFormat::Ip) },
-            "ipv4" => quote! { Some(paperclip::v2::models::DataTypeFormat::IpV4) },
-            "ipv6" => quote! { Some(paperclip::v2::models::DataTypeFormat::IpV6) },
-            "other" => quote! { Some(paperclip::v2::models::DataTypeFormat::Other) },
-            v => {
-                emit_error!(
-                    format.span().unwrap(),
-                    format!("Invalid format attribute value. Got {}", v)
-                );
-                quote! { None }
-            }
-        }
-    }
-
     let struct_ast = match &item_ast.data {
         Data::Struct(struct_ast) => struct_ast,
         Data::Enum(_) | Data::Union(_) => {
@@ -1426,6 +3095,7 @@ fn add_response_operation_modifier_impl(
 fn get_field_type(field: &Field) -> Option<proc_macro2::TokenStream> {
     match field.ty {
         Type::Path(_) | Type::Reference(_) => Some(address_type_for_fn_call(&field.ty)),
+        Type::Tuple(ref t) if !t.elems.is_empty() => Some(address_type_for_fn_call(&field.ty)),
         _ => {
             emit_warning!(
                 field.ty.span().unwrap(),
@@ -1455,14 +3125,29 @@ fn handle_unnamed_field_struct(
                     if !#docs.is_empty() {
                         s.description = Some(#docs.to_string());
                     }
+                    s.example = schema.example.take();
                     schema = s;
                 }));
             } else {
+                // Newtypes are inlined, so their format has to be set on the inner schema.
+                let format =
+                    match extract_format(struct_attr).or_else(|| extract_format(&field.attrs)) {
+                        Some(format) => {
+                            let format = quote_format(&format);
+                            quote!(s.format = #format;)
+                        }
+                        None => quote!(),
+                    };
                 props_gen.extend(quote!({
                     let mut s = #ty_ref::raw_schema();
                     if !#docs.is_empty() {
                         s.description = Some(#docs.to_string());
                     }
+                    #format
+                    // Example of the wrapper takes precedence over the inner one.
+                    if let Some(example) = schema.example.take() {
+                        s.example = Some(example);
+                    }
                     schema = s;
                 }));
             }
@@ -1475,7 +3160,8 @@ fn handle_unnamed_field_struct(
 
             let ty_ref = get_field_type(field);
 
-            let docs = extract_documentation(&field.attrs);
+            let docs = extract_description(&field.attrs)
+                .unwrap_or_else(|| extract_documentation(&field.attrs));
             let docs = docs.trim();
 
             let override_required = OpenApiRequired::exists(&field.attrs);
@@ -1502,33 +3188,219 @@ fn handle_unnamed_field_struct(
                     if #ty_ref::required() {
                         schema.required.extend(s.required);
                     }
-                })
-            };
-
-            props_gen.extend(gen);
+                })
+            };
+
+            props_gen.extend(gen);
+        }
+    }
+}
+
+/// Checks for `api_v2_empty` attributes and removes them.
+fn extract_openapi_attrs(
+    field_attrs: &'_ [Attribute],
+) -> impl Iterator<Item = Punctuated<syn::NestedMeta, syn::token::Comma>> + '_ {
+    parse_openapi_attrs(field_attrs).map(|attrs| {
+        attrs
+            .0
+            .into_iter()
+            .filter_map(|attr| match attr {
+                MacroAttr::Meta(meta) => Some(meta),
+                MacroAttr::Pairs(..) => None,
+            })
+            .collect()
+    })
+}
+
+fn parse_openapi_attrs(field_attrs: &'_ [Attribute]) -> impl Iterator<Item = MacroAttrs> + '_ {
+    field_attrs
+        .iter()
+        .filter(|a| a.path.is_ident(SCHEMA_MACRO_ATTR))
+        .filter_map(|a| a.parse_args::<MacroAttrs>().ok())
+}
+
+/// Generates code inserting the `#[openapi(extension("x-foo" = "bar"))]` extensions
+/// into the given schema.
+fn extract_extensions(attrs: &[Attribute], schema: &Ident) -> proc_macro2::TokenStream {
+    let mut keys = vec![];
+    let mut values = vec![];
+    for attr in parse_openapi_attrs(attrs).flat_map(|attrs| attrs.0.into_iter()) {
+        match attr {
+            MacroAttr::Pairs(ident, list) if ident == "extension" => {
+                for (key, value) in extension_pairs(list) {
+                    keys.push(key);
+                    values.push(value);
+                }
+            }
+            MacroAttr::Meta(NestedMeta::Meta(Meta::List(list)))
+                if list.path.is_ident("extension") =>
+            {
+                emit_error!(
+                    list.span().unwrap(),
+                    format!(
+                        "`#[{}(extension(\"x-...\" = ...))]` expects string keys",
+                        SCHEMA_MACRO_ATTR
+                    ),
+                );
+            }
+            _ => (),
+        }
+    }
+
+    quote!(
+        #(
+            #schema.extensions.insert(#keys.into(), serde_json::json!(#values));
+        )*
+    )
+}
+
+/// Validates the given `"x-foo" = value` extension pairs. Values are literals,
+/// which end up as JSON strings, numbers or booleans.
+fn extension_pairs(list: Vec<(Lit, Lit)>) -> Vec<(LitStr, Lit)> {
+    list.into_iter()
+        .filter_map(|(key, value)| match key {
+            Lit::Str(key) if key.value().starts_with("x-") => Some((key, value)),
+            _ => {
+                emit_error!(
+                    key.span().unwrap(),
+                    "Extension names must be string literals starting with \"x-\""
+                );
+                None
+            }
+        })
+        .collect()
+}
+
+/// Generates code applying the `validator` crate's `#[validate(range(...))]`,
+/// `#[validate(length(...))]` and `#[validate(regex = "...")]` constraints
+/// to the given schema.
+fn extract_validations(attrs: &[Attribute], schema: &Ident) -> proc_macro2::TokenStream {
+    let mut gen = quote!();
+    if !cfg!(feature = "validator") {
+        return gen;
+    }
+
+    let validations = attrs
+        .iter()
+        .filter(|a| a.path.is_ident("validate"))
+        .filter_map(|a| match a.parse_meta() {
+            Ok(Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flat_map(|nested| nested.into_iter());
+
+    for validation in validations {
+        match validation {
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("range") => {
+                for (bound, value) in validation_bounds(list) {
+                    let value = match value {
+                        Lit::Int(i) => i.base10_parse::<f32>().ok(),
+                        Lit::Float(f) => f.base10_parse::<f32>().ok(),
+                        _ => None,
+                    };
+                    match (bound.as_str(), value) {
+                        ("min", Some(v)) => gen.extend(quote!(#schema.minimum = Some(#v);)),
+                        ("max", Some(v)) => gen.extend(quote!(#schema.maximum = Some(#v);)),
+                        _ => (),
+                    }
+                }
+            }
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("length") => {
+                for (bound, value) in validation_bounds(list) {
+                    let value = match value {
+                        Lit::Int(i) => i.base10_parse::<u32>().ok(),
+                        _ => None,
+                    };
+                    let (min, max) = match (bound.as_str(), value) {
+                        ("min", Some(v)) => (Some(v), None),
+                        ("max", Some(v)) => (None, Some(v)),
+                        ("equal", Some(v)) => (Some(v), Some(v)),
+                        _ => continue,
+                    };
+                    // Lengths of anything other than strings aren't expressible here.
+                    if let Some(min) = min {
+                        gen.extend(quote!(
+                            if #schema.data_type == Some(paperclip::v2::models::DataType::String) {
+                                #schema.min_length = Some(#min);
+                            }
+                        ));
+                    }
+                    if let Some(max) = max {
+                        gen.extend(quote!(
+                            if #schema.data_type == Some(paperclip::v2::models::DataType::String) {
+                                #schema.max_length = Some(#max);
+                            }
+                        ));
+                    }
+                }
+            }
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("regex") => {
+                gen.extend(quote_regex(&nv.lit, schema));
+            }
+            NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("regex") => {
+                for (key, value) in validation_bounds(list) {
+                    if key == "path" {
+                        gen.extend(quote_regex(&value, schema));
+                    }
+                }
+            }
+            _ => (),
         }
     }
+
+    gen
 }
 
-/// Checks for `api_v2_empty` attributes and removes them.
-fn extract_openapi_attrs(
-    field_attrs: &'_ [Attribute],
-) -> impl Iterator<Item = Punctuated<syn::NestedMeta, syn::token::Comma>> + '_ {
-    field_attrs.iter().filter_map(|a| match a.parse_meta() {
-        Ok(Meta::List(list)) if list.path.is_ident(SCHEMA_MACRO_ATTR) => Some(list.nested),
-        _ => None,
-    })
+/// Collects the `key = literal` pairs of a `#[validate(...)]` validator.
+fn validation_bounds(list: MetaList) -> Vec<(String, Lit)> {
+    list.nested
+        .into_iter()
+        .filter_map(|nested| match nested {
+            NestedMeta::Meta(Meta::NameValue(nv)) => {
+                let key = nv.path.get_ident()?.to_string();
+                Some((key, nv.lit))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The `validator` crate refers to regexes by the path of a static, so the pattern
+/// is taken from that static when generating the schema.
+fn quote_regex(lit: &Lit, schema: &Ident) -> proc_macro2::TokenStream {
+    match lit {
+        Lit::Str(s) => match s.parse::<Path>() {
+            Ok(path) => quote!(#schema.pattern = Some(#path.as_str().to_owned());),
+            Err(e) => e.to_compile_error(),
+        },
+        _ => quote!(),
+    }
 }
 
 fn extract_deprecated(attrs: &[Attribute]) -> bool {
-    attrs.iter().any(|a| match a.parse_meta() {
-        Ok(Meta::Path(mp)) if mp.is_ident("deprecated") => true,
-        Ok(Meta::List(mml)) => mml
-            .path
-            .segments
-            .into_iter()
-            .any(|p| p.ident == "deprecated"),
-        _ => false,
+    extract_rust_deprecation(attrs).is_some()
+}
+
+/// Extracts Rust's `#[deprecated]` attribute along with its note, if any, i.e.
+/// `#[deprecated = "..."]` or `#[deprecated(note = "...")]`.
+fn extract_rust_deprecation(attrs: &[Attribute]) -> Option<Option<String>> {
+    attrs.iter().find_map(|a| match a.parse_meta() {
+        Ok(Meta::Path(mp)) if mp.is_ident("deprecated") => Some(None),
+        Ok(Meta::NameValue(mnv)) if mnv.path.is_ident("deprecated") => match mnv.lit {
+            Lit::Str(note) => Some(Some(note.value())),
+            _ => Some(None),
+        },
+        Ok(Meta::List(mml)) if mml.path.is_ident("deprecated") => {
+            Some(mml.nested.iter().find_map(|meta| match meta {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(note),
+                    ..
+                })) if path.is_ident("note") => Some(note.value()),
+                _ => None,
+            }))
+        }
+        _ => None,
     })
 }
 
@@ -1547,14 +3419,60 @@ fn extract_documentation(attrs: &[Attribute]) -> String {
         .join("\n")
 }
 
+/// Quotes the given data type format (`None` if it's invalid).
+fn quote_format(format: &str) -> proc_macro2::TokenStream {
+    match format {
+        "int32" => quote! { Some(paperclip::v2::models::DataTypeFormat::Int32) },
+        "int64" => quote! { Some(paperclip::v2::models::DataTypeFormat::Int64) },
+        "float" => quote! { Some(paperclip::v2::models::DataTypeFormat::Float) },
+        "double" => quote! { Some(paperclip::v2::models::DataTypeFormat::Double) },
+        "byte" => quote! { Some(paperclip::v2::models::DataTypeFormat::Byte) },
+        "binary" => quote! { Some(paperclip::v2::models::DataTypeFormat::Binary) },
+        "date" => quote! { Some(paperclip::v2::models::DataTypeFormat::Date) },
+        "datetime" | "date-time" => {
+            quote! { Some(paperclip::v2::models::DataTypeFormat::DateTime) }
+        }
+        "password" => quote! { Some(paperclip::v2::models::DataTypeFormat::Password) },
+        "url" => quote! { Some(paperclip::v2::models::DataTypeFormat::Url) },
+        "uuid" => quote! { Some(paperclip::v2::models::DataTypeFormat::Uuid) },
+        "ip" => quote! { Some(paperclip::v2::models::DataTypeFormat::Ip) },
+        "ipv4" => quote! { Some(paperclip::v2::models::DataTypeFormat::IpV4) },
+        "ipv6" => quote! { Some(paperclip::v2::models::DataTypeFormat::IpV6) },
+        "decimal" => quote! { Some(paperclip::v2::models::DataTypeFormat::Decimal) },
+        "int128" => quote! { Some(paperclip::v2::models::DataTypeFormat::Int128) },
+        "email" => quote! { Some(paperclip::v2::models::DataTypeFormat::Email) },
+        "other" => quote! { Some(paperclip::v2::models::DataTypeFormat::Other) },
+        v => {
+            emit_error!(
+                format.span().unwrap(),
+                format!("Invalid format attribute value. Got {}", v)
+            );
+            quote! { None }
+        }
+    }
+}
+
 /// Checks if an empty schema has been requested and generate if needed.
 fn check_empty_schema(item_ast: &DeriveInput) -> Option<TokenStream> {
+    // `#[openapi(empty)]` or `#[openapi(empty = "reason")]`, where the reason
+    // only documents why the schema is intentionally empty.
     let needs_empty_schema = extract_openapi_attrs(&item_ast.attrs).any(|nested| {
-        nested.len() == 1
-            && match &nested[0] {
-                NestedMeta::Meta(Meta::Path(path)) => path.is_ident("empty"),
-                _ => false,
+        nested.iter().any(|meta| match meta {
+            NestedMeta::Meta(Meta::Path(path)) => path.is_ident("empty"),
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("empty") => {
+                if !matches!(nv.lit, Lit::Str(_)) {
+                    emit_error!(
+                        nv.lit.span().unwrap(),
+                        format!(
+                            "`#[{}(empty = \"...\")]` expects a string argument",
+                            SCHEMA_MACRO_ATTR
+                        ),
+                    );
+                }
+                true
             }
+            _ => false,
+        })
     });
 
     if needs_empty_schema {
@@ -1599,11 +3517,15 @@ fn handle_field_struct(
             .strip_prefix("r#")
             .map(|n| n.to_string())
             .unwrap_or(field_name);
-        if SerdeSkip::exists(&field.attrs) {
+        let skip_serializing = SerdeSkipOneWay::serializing(&field.attrs);
+        let skip_deserializing = SerdeSkipOneWay::deserializing(&field.attrs);
+        if SerdeSkip::exists(&field.attrs) || (skip_serializing && skip_deserializing) {
             continue;
         }
 
-        if let Some(renamed) = SerdeRename::from_field_attrs(&field.attrs) {
+        if let Some(renamed) = extract_rename(&field.attrs) {
+            field_name = renamed;
+        } else if let Some(renamed) = SerdeRename::from_field_attrs(&field.attrs) {
             field_name = renamed;
         } else if let Some(prop) = serde.rename {
             field_name = prop.rename(&field_name);
@@ -1611,8 +3533,21 @@ fn handle_field_struct(
 
         let ty_ref = get_field_type(field);
 
-        let docs = extract_documentation(&field.attrs);
-        let docs = docs.trim();
+        // Explicit description takes precedence over the doc comment.
+        let docs = extract_description(&field.attrs)
+            .unwrap_or_else(|| extract_documentation(&field.attrs));
+        let mut docs = docs.trim().to_owned();
+
+        // Deprecation notes are appended to the description to guide the migration.
+        let deprecated_note = extract_deprecated_note(&field.attrs)
+            .or_else(|| extract_rust_deprecation(&field.attrs));
+        let deprecated = deprecated_note.is_some();
+        if let Some(Some(note)) = deprecated_note {
+            if !docs.is_empty() {
+                docs.push_str("\n\n");
+            }
+            docs.push_str(&format!("Deprecated: {}", note.trim()));
+        }
 
         let example = if let Some(example) = extract_example(&field.attrs) {
             // allow to parse escaped json string or single str value
@@ -1623,7 +3558,63 @@ fn handle_field_struct(
             quote!({})
         };
 
+        // Optional fields may also be null (if enabled). Enumerations have to list `null`
+        // explicitly, while references are made nullable when converting to v3.
+        let nullable = if cfg!(feature = "option-nullable") {
+            quote!(
+                s.nullable = !#ty_ref::required();
+                if s.nullable && s.reference.is_none() && !s.enum_.is_empty() && !s.enum_.contains(&serde_json::Value::Null) {
+                    s.enum_.push(serde_json::Value::Null);
+                }
+            )
+        } else {
+            quote!()
+        };
+
+        let extensions = extract_extensions(
+            &field.attrs,
+            &Ident::new("s", proc_macro2::Span::call_site()),
+        );
+
+        let xml = match extract_xml(&field.attrs) {
+            Some(xml) => quote!(s.xml = Some(#xml);),
+            None => quote!(),
+        };
+
+        let enum_values = match extract_enum_values(&field.attrs) {
+            Some(values) => quote!(s.enum_ = vec![#(serde_json::json!(#values)),*];),
+            None => quote!(),
+        };
+
+        let validations = extract_validations(
+            &field.attrs,
+            &Ident::new("s", proc_macro2::Span::call_site()),
+        );
+
+        let format = extract_format(&field.attrs);
+        // Passwords are only ever sent by clients, unless stated otherwise.
+        let write_only = skip_serializing
+            || extract_write_only(&field.attrs)
+                .unwrap_or_else(|| format.as_deref() == Some("password"));
+        let format = match format {
+            Some(format) => {
+                let format = quote_format(&format);
+                quote!(s.format = #format;)
+            }
+            None => quote!(),
+        };
+        // An explicit pattern takes precedence over the one from the validator.
+        let pattern = match extract_pattern(&field.attrs) {
+            Some(pattern) => quote!(s.pattern = Some(#pattern.into());),
+            None => quote!(),
+        };
+
         let override_required = OpenApiRequired::exists(&field.attrs);
+        let allow_empty_value = OpenApiAllowEmptyValue::exists(&field.attrs);
+        let style = match extract_style(&field.attrs) {
+            Some(style) => quote!(s.style = Some(#style);),
+            None => quote!(),
+        };
         let gen = if !SerdeFlatten::exists(&field.attrs) {
             quote!({
                 let mut s = #ty_ref::raw_schema();
@@ -1631,9 +3622,21 @@ fn handle_field_struct(
                     s.description = Some(#docs.to_string());
                 }
                 #example;
+                #extensions
+                #format
+                #xml
+                #enum_values
+                #validations
+                #pattern
+                s.write_only = #write_only;
+                s.read_only = #skip_deserializing;
+                s.deprecated = #deprecated;
+                s.allow_empty_value = #allow_empty_value;
+                #style
+                #nullable
                 schema.properties.insert(#field_name.into(), s.into());
 
-                if #ty_ref::required() || #override_required {
+                if (#ty_ref::required() || #override_required) && !#skip_serializing {
                     schema.required.insert(#field_name.into());
                 }
             })
@@ -1652,45 +3655,271 @@ fn handle_field_struct(
     }
 }
 
-/// Generates code for an enum (if supported).
-fn handle_enum(e: &DataEnum, serde: &SerdeProps, props_gen: &mut proc_macro2::TokenStream) {
+/// Generates the integer enumeration of a C-like enum from its discriminants.
+fn handle_int_enum(e: &DataEnum, props_gen: &mut proc_macro2::TokenStream) {
     props_gen.extend(quote!(
-        schema.data_type = Some(DataType::String);
+        schema.data_type = Some(DataType::Integer);
     ));
 
+    let mut next = 0i64;
     for var in &e.variants {
-        let mut name = var.ident.to_string();
-        match &var.fields {
-            Fields::Unit => (),
-            Fields::Named(ref f) => {
-                emit_warning!(
-                    f.span().unwrap(),
-                    "skipping enum variant with named fields in schema."
-                );
-                continue;
-            }
-            Fields::Unnamed(ref f) => {
-                emit_warning!(f.span().unwrap(), "skipping tuple enum variant in schema.");
-                continue;
-            }
+        if !matches!(var.fields, Fields::Unit) {
+            emit_error!(
+                var.span().unwrap(),
+                "only unit variants are supported for integer enums"
+            );
+            continue;
+        }
+
+        let value = match &var.discriminant {
+            Some((_, expr)) => match discriminant_value(expr) {
+                Some(v) => v,
+                None => {
+                    emit_error!(
+                        expr.span().unwrap(),
+                        "integer enums expect literal discriminants"
+                    );
+                    continue;
+                }
+            },
+            None => next,
+        };
+        next = value + 1;
+
+        if SerdeSkip::exists(&var.attrs) {
+            continue;
         }
 
+        props_gen.extend(quote!(
+            schema.enum_.push(serde_json::json!(#value));
+        ));
+    }
+}
+
+/// Evaluates an integer literal discriminant, e.g. `3` or `-1`.
+fn discriminant_value(expr: &syn::Expr) -> Option<i64> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: Lit::Int(i), ..
+        }) => i.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => discriminant_value(expr).map(|v| -v),
+        syn::Expr::Group(g) => discriminant_value(&g.expr),
+        syn::Expr::Paren(p) => discriminant_value(&p.expr),
+        _ => None,
+    }
+}
+
+/// Generates code for an enum (if supported). Variants of non-plain enums are emitted
+/// as `anyOf` (or `oneOf` if they're marked as mutually exclusive).
+fn handle_enum(
+    e: &DataEnum,
+    serde: &SerdeProps,
+    one_of: bool,
+    collapsed: bool,
+    props_gen: &mut proc_macro2::TokenStream,
+) {
+    let variants = if one_of {
+        quote!(one_of)
+    } else {
+        quote!(any_of)
+    };
+    // Externally tagged enums with only unit variants are serialized as plain strings.
+    let is_plain = serde.tag.is_none()
+        && !serde.untagged
+        && e.variants
+            .iter()
+            .all(|var| matches!(var.fields, Fields::Unit));
+    if is_plain {
+        props_gen.extend(quote!(
+            schema.data_type = Some(DataType::String);
+        ));
+    }
+
+    let mut unit_names = vec![];
+    let mut variants_gen = quote!();
+    for var in &e.variants {
         if SerdeSkip::exists(&var.attrs) {
             continue;
         }
 
+        let mut name = var.ident.to_string();
         if let Some(renamed) = SerdeRename::from_field_attrs(&var.attrs) {
             name = renamed;
         } else if let Some(prop) = serde.rename {
             name = prop.rename(&name);
         }
 
+        let priority = extract_priority(&var.attrs);
+        // Unit variants end up as plain values of a single string schema.
+        let warn_unit_priority = || {
+            if priority.is_some() {
+                emit_warning!(
+                    var.span().unwrap(),
+                    "priority of unit variant is ignored, as it's only a value of the string schema."
+                );
+            }
+        };
+
+        if is_plain {
+            warn_unit_priority();
+            props_gen.extend(quote!(
+                schema.enum_.push(serde_json::json!(#name));
+            ));
+            continue;
+        }
+
+        let content = enum_variant_content(var);
+        let tag_schema = quote!({
+            let mut s = DefaultSchemaRaw {
+                data_type: Some(DataType::String),
+                ..Default::default()
+            };
+            s.enum_.push(serde_json::json!(#name));
+            s
+        });
+
+        let gen = match (&serde.tag, &serde.content, content) {
+            (_, _, Some(content)) if serde.untagged => content,
+            (_, _, None) if serde.untagged => {
+                emit_warning!(
+                    var.span().unwrap(),
+                    "skipping unit variant of untagged enum in schema."
+                );
+                continue;
+            }
+            // Externally tagged: `"Variant"` or `{ "Variant": content }`
+            (None, _, None) => {
+                warn_unit_priority();
+                unit_names.push(name);
+                continue;
+            }
+            (None, _, Some(content)) => quote!({
+                let mut s = DefaultSchemaRaw {
+                    data_type: Some(DataType::Object),
+                    ..Default::default()
+                };
+                s.properties.insert(#name.into(), #content.into());
+                s.required.insert(#name.into());
+                s
+            }),
+            // Internally tagged: `{ "tag": "Variant", ...content }`
+            (Some(tag), None, content) => {
+                if matches!(&var.fields, Fields::Unnamed(f) if f.unnamed.len() > 1) {
+                    emit_warning!(
+                        var.span().unwrap(),
+                        "skipping tuple variant of internally tagged enum in schema."
+                    );
+                    continue;
+                }
+
+                let content = content.unwrap_or_else(|| {
+                    quote!(DefaultSchemaRaw {
+                        data_type: Some(DataType::Object),
+                        ..Default::default()
+                    })
+                });
+                quote!({
+                    let mut s = #content;
+                    s.properties.insert(#tag.into(), #tag_schema.into());
+                    s.required.insert(#tag.into());
+                    s
+                })
+            }
+            // Adjacently tagged: `{ "tag": "Variant", "content": content }`
+            (Some(tag), Some(content_name), content) => {
+                let content_gen = content.map(|content| {
+                    quote!(
+                        s.properties.insert(#content_name.into(), #content.into());
+                        s.required.insert(#content_name.into());
+                    )
+                });
+                quote!({
+                    let mut s = DefaultSchemaRaw {
+                        data_type: Some(DataType::Object),
+                        ..Default::default()
+                    };
+                    s.properties.insert(#tag.into(), #tag_schema.into());
+                    s.required.insert(#tag.into());
+                    #content_gen
+                    s
+                })
+            }
+        };
+
+        let docs = extract_documentation(&var.attrs);
+        let docs = docs.trim();
+        let priority = priority.map(|p| {
+            quote!(
+                s.extensions.insert(#PRIORITY_EXTENSION.into(), serde_json::json!(#p));
+            )
+        });
+        variants_gen.extend(quote!({
+            let mut s = #gen;
+            if !#docs.is_empty() {
+                s.description = Some(#docs.to_string());
+            }
+            #priority
+            schema.#variants.push(s.into());
+        }));
+    }
+
+    if !unit_names.is_empty() {
+        props_gen.extend(quote!({
+            let mut s = DefaultSchemaRaw {
+                data_type: Some(DataType::String),
+                ..Default::default()
+            };
+            #(
+                s.enum_.push(serde_json::json!(#unit_names));
+            )*
+            schema.#variants.push(s.into());
+        }));
+    }
+
+    props_gen.extend(variants_gen);
+
+    // A single variant doesn't need the wrapper, so its schema is used directly.
+    if collapsed {
         props_gen.extend(quote!(
-            schema.enum_.push(serde_json::json!(#name));
+            if schema.#variants.len() == 1 {
+                let mut s = *schema.#variants.remove(0);
+                s.example = schema.example.take().or(s.example);
+                s.example_ref = schema.example_ref.take();
+                s.xml = schema.xml.take().or(s.xml);
+                schema = s;
+            }
         ));
     }
 }
 
+/// Generates the schema for the data held by an enum variant (if any). Like serde,
+/// single element tuple variants use the inner schema directly, whereas other tuple
+/// variants are keyed by their index.
+fn enum_variant_content(var: &Variant) -> Option<proc_macro2::TokenStream> {
+    let mut gen = quote!();
+    match &var.fields {
+        Fields::Unit => return None,
+        Fields::Named(ref f) => {
+            let props = SerdeProps::from_item_attrs(&var.attrs);
+            handle_field_struct(f, &[], &props, &mut gen)
+        }
+        Fields::Unnamed(ref f) => handle_unnamed_field_struct(f, &[], &mut gen),
+    }
+
+    Some(quote!({
+        let mut schema = DefaultSchemaRaw {
+            data_type: Some(DataType::Object),
+            ..Default::default()
+        };
+        #gen
+        schema
+    }))
+}
+
 /// An associated function of a generic type, say, a vector cannot be called
 /// like `Vec::foo` as it doesn't have a default type. We should instead call
 /// `Vec::<T>::foo`. Something similar applies to `str`. This function takes
@@ -1699,8 +3928,11 @@ fn address_type_for_fn_call(old_ty: &Type) -> proc_macro2::TokenStream {
     if let Type::Reference(_) = old_ty {
         return quote!(<(#old_ty)>);
     }
+    if let Type::Tuple(_) = old_ty {
+        return quote!(paperclip::v2::schema::TupleSchema::<#old_ty>);
+    }
 
-    let mut ty = old_ty.clone();
+    let mut ty = unwrap_smart_pointers(old_ty);
     if let Type::Path(ref mut p) = &mut ty {
         p.path.segments.pairs_mut().for_each(|mut pair| {
             let is_empty = pair.value().arguments.is_empty();
@@ -1717,6 +3949,42 @@ fn address_type_for_fn_call(old_ty: &Type) -> proc_macro2::TokenStream {
     quote!(#ty)
 }
 
+/// Replaces smart pointers (`Box<T>`, `Rc<T>` and `Arc<T>`) in the given type
+/// with the (sized) types they point to, as they're transparent in the schema.
+fn unwrap_smart_pointers(ty: &Type) -> Type {
+    let mut ty = ty.clone();
+    if let Type::Path(ref mut p) = ty {
+        if let Some(last) = p.path.segments.last() {
+            if ["Box", "Rc", "Arc"].iter().any(|name| last.ident == name) {
+                if let PathArguments::AngleBracketed(args) = &last.arguments {
+                    match (args.args.len(), args.args.first()) {
+                        // Unsized types like `Box<str>` have their own schemas.
+                        (1, Some(GenericArgument::Type(Type::Slice(_)))) => (),
+                        (1, Some(GenericArgument::Type(Type::Path(inner))))
+                            if inner.path.is_ident("str") => {}
+                        (1, Some(GenericArgument::Type(inner))) => {
+                            return unwrap_smart_pointers(inner)
+                        }
+                        _ => (),
+                    }
+                }
+            }
+        }
+
+        for segment in p.path.segments.iter_mut() {
+            if let PathArguments::AngleBracketed(ref mut args) = segment.arguments {
+                for arg in args.args.iter_mut() {
+                    if let GenericArgument::Type(ref mut inner) = arg {
+                        *inner = unwrap_smart_pointers(inner);
+                    }
+                }
+            }
+        }
+    }
+
+    ty
+}
+
 /* Serde attributes */
 
 /// Supported renaming options in serde (https://serde.rs/variant-attrs.html).
@@ -1798,8 +4066,7 @@ impl SerdeRename {
 
 /// Serde skip (https://serde.rs/variant-attrs.html)
 /// Never serialize or deserialize this variant.
-/// There are other variants available (skip_serializing,skip_deserializing) though it's not clear
-/// how this should be handled since we use the same Schema for Ser/DeSer
+/// The one-way variants (skip_serializing, skip_deserializing) are handled by `SerdeSkipOneWay`.
 struct SerdeSkip;
 
 impl SerdeSkip {
@@ -1834,6 +4101,53 @@ impl SerdeSkip {
     }
 }
 
+/// Serde one-way skips (https://serde.rs/field-attrs.html), i.e. `skip_serializing` and
+/// `skip_deserializing` (also accepted in the `openapi` attribute).
+///
+/// We can't drop these fields, because the same schema is used for both requests and
+/// responses. Instead, this is approximated by marking fields that are never serialized
+/// as `writeOnly` (and not required, as they never show up in responses) and fields
+/// that are never deserialized as `readOnly`.
+struct SerdeSkipOneWay;
+
+impl SerdeSkipOneWay {
+    /// Returns whether the field is never serialized.
+    fn serializing(field_attrs: &[Attribute]) -> bool {
+        Self::exists(field_attrs, "skip_serializing")
+    }
+
+    /// Returns whether the field is never deserialized.
+    fn deserializing(field_attrs: &[Attribute]) -> bool {
+        Self::exists(field_attrs, "skip_deserializing")
+    }
+
+    fn exists(field_attrs: &[Attribute], marker: &str) -> bool {
+        for meta in field_attrs.iter().filter_map(|a| a.parse_meta().ok()) {
+            let inner_meta = match meta {
+                Meta::List(ref l)
+                    if l.path
+                        .segments
+                        .last()
+                        .map(|p| p.ident == "serde" || p.ident == SCHEMA_MACRO_ATTR)
+                        .unwrap_or(false) =>
+                {
+                    &l.nested
+                }
+                _ => continue,
+            };
+            for meta in inner_meta {
+                if let NestedMeta::Meta(Meta::Path(path)) = meta {
+                    if path.is_ident(marker) {
+                        return true;
+                    }
+                }
+            }
+        }
+
+        false
+    }
+}
+
 /// Custom attribute that sets this attribute as required, even if the type is not required.
 struct OpenApiRequired;
 
@@ -1872,6 +4186,12 @@ impl OpenApiRequired {
 #[derive(Clone, Debug, Default)]
 struct SerdeProps {
     rename: Option<SerdeRename>,
+    /// Field name for the variant tag (`#[serde(tag = "...")]`).
+    tag: Option<String>,
+    /// Field name for the variant content (`#[serde(content = "...")]`).
+    content: Option<String>,
+    /// Whether the enum has been marked with `#[serde(untagged)]`.
+    untagged: bool,
 }
 
 impl SerdeProps {
@@ -1894,34 +4214,122 @@ impl SerdeProps {
             };
 
             for meta in inner_meta {
-                let global_rename = match meta {
-                    NestedMeta::Meta(Meta::NameValue(ref v))
-                        if v.path
-                            .segments
-                            .last()
-                            .map(|p| p.ident == "rename_all")
-                            .unwrap_or(false) =>
-                    {
-                        &v.lit
+                match meta {
+                    NestedMeta::Meta(Meta::NameValue(ref v)) => {
+                        let value = match &v.lit {
+                            Lit::Str(s) => s.value(),
+                            _ => continue,
+                        };
+
+                        if v.path.is_ident("rename_all") {
+                            props.rename = value.parse().ok();
+                        } else if v.path.is_ident("tag") {
+                            props.tag = Some(value);
+                        } else if v.path.is_ident("content") {
+                            props.content = Some(value);
+                        }
                     }
-                    _ => continue,
-                };
-
-                if let Lit::Str(ref s) = global_rename {
-                    props.rename = s.value().parse().ok();
+                    NestedMeta::Meta(Meta::Path(ref p)) if p.is_ident("untagged") => {
+                        props.untagged = true;
+                    }
+                    _ => (),
                 }
             }
         }
 
+        // Renaming can also be specified without serde, i.e. `#[openapi(rename_all = "...")]`.
+        if props.rename.is_none() {
+            props.rename = extract_openapi_attrs(item_attrs)
+                .flatten()
+                .find_map(|meta| match meta {
+                    NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename_all") => {
+                        match nv.lit {
+                            Lit::Str(s) => Some(s),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+                .and_then(|s| match s.value().parse() {
+                    Ok(rename) => Some(rename),
+                    Err(_) => {
+                        emit_error!(s.span().unwrap(), "unsupported `rename_all` value");
+                        None
+                    }
+                });
+        }
+
         props
     }
 }
 
+/// Marker for (query) fields which accept empty values, i.e. `#[openapi(allow_empty_value)]`.
+struct OpenApiAllowEmptyValue;
+
+impl OpenApiAllowEmptyValue {
+    /// Returns whether the parameter generated from this field allows empty values.
+    fn exists(field_attrs: &[Attribute]) -> bool {
+        extract_openapi_attrs(field_attrs).flatten().any(|meta| {
+            matches!(meta, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("allow_empty_value"))
+        })
+    }
+}
+
+/// Marker for enums with mutually exclusive variants, i.e. `#[openapi(one_of)]`.
+struct OpenApiOneOf;
+
+impl OpenApiOneOf {
+    /// Returns whether the enum variants should be emitted as `oneOf` (instead of `anyOf`).
+    fn exists(item_attrs: &[Attribute]) -> bool {
+        extract_openapi_attrs(item_attrs).flatten().any(
+            |meta| matches!(meta, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("one_of")),
+        )
+    }
+}
+
+/// Marker for enums whose single variant is used in place of the enum, i.e. `#[openapi(collapsed)]`.
+struct OpenApiCollapsed;
+
+impl OpenApiCollapsed {
+    /// Returns whether a single-variant enum should skip the `anyOf` (or `oneOf`) wrapper.
+    fn exists(item_attrs: &[Attribute]) -> bool {
+        extract_openapi_attrs(item_attrs).flatten().any(
+            |meta| matches!(meta, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("collapsed")),
+        )
+    }
+}
+
+/// Marker for types whose empty (object) schema is inlined as `{}` instead of being added to
+/// the definitions, i.e. `#[openapi(skip_if_empty)]`.
+struct OpenApiSkipIfEmpty;
+
+impl OpenApiSkipIfEmpty {
+    /// Returns whether the empty schema of this type should be skipped from the definitions.
+    fn exists(item_attrs: &[Attribute]) -> bool {
+        extract_openapi_attrs(item_attrs).flatten().any(|meta| {
+            matches!(meta, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip_if_empty"))
+        })
+    }
+}
+
+/// Marker for types whose example is their serialized default value, i.e. `#[openapi(example_from_default)]`.
+struct OpenApiExampleFromDefault;
+
+impl OpenApiExampleFromDefault {
+    /// Returns whether the example of this type should be generated from its `Default` impl.
+    fn exists(item_attrs: &[Attribute]) -> bool {
+        extract_openapi_attrs(item_attrs).flatten().any(|meta| {
+            matches!(meta, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("example_from_default"))
+        })
+    }
+}
+
 /// Supported flattening of embedded struct (https://serde.rs/variant-attrs.html).
 struct SerdeFlatten;
 
 impl SerdeFlatten {
-    /// Traverses the field attributes and returns true if there is `#[serde(flatten)]`.
+    /// Traverses the field attributes and returns true if there is `#[serde(flatten)]`
+    /// (or our own `#[openapi(flatten)]` for types not using serde).
     fn exists(field_attrs: &[Attribute]) -> bool {
         for meta in field_attrs.iter().filter_map(|a| a.parse_meta().ok()) {
             let inner_meta = match meta {
@@ -1929,7 +4337,7 @@ impl SerdeFlatten {
                     if l.path
                         .segments
                         .last()
-                        .map(|p| p.ident == "serde")
+                        .map(|p| p.ident == "serde" || p.ident == SCHEMA_MACRO_ATTR)
                         .unwrap_or(false) =>
                 {
                     &l.nested
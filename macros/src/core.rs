@@ -63,7 +63,12 @@ pub fn emit_v2_schema_struct(input: TokenStream) -> TokenStream {
                 self.properties.values_mut().for_each(|s| s.remove_refs());
                 self.items.as_mut().map(|s| s.remove_refs());
                 self.extra_props.as_mut().and_then(|s| s.right_mut()).map(|s| s.remove_refs());
-                self.reference = None;
+                self.any_of.iter_mut().for_each(|s| s.remove_refs());
+                self.one_of.iter_mut().for_each(|s| s.remove_refs());
+                self.all_of.iter_mut().for_each(|s| s.remove_refs());
+                if !self.cyclic {
+                    self.reference = None;
+                }
             }
 
             /// Recursively removes all properties other than `$ref` value
@@ -77,6 +82,9 @@ pub fn emit_v2_schema_struct(input: TokenStream) -> TokenStream {
                     self.properties.values_mut().for_each(|s| s.retain_ref());
                     self.items.as_mut().map(|s| s.retain_ref());
                     self.extra_props.as_mut().and_then(|s| s.right_mut()).map(|s| s.retain_ref());
+                    self.any_of.iter_mut().for_each(|s| s.retain_ref());
+                    self.one_of.iter_mut().for_each(|s| s.retain_ref());
+                    self.all_of.iter_mut().for_each(|s| s.retain_ref());
                 }
             }
         }
@@ -288,6 +296,69 @@ fn schema_fields(name: &Ident, is_ref: bool) -> proc_macro2::TokenStream {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub example: Option<serde_json::Value>,
     ));
+    gen.extend(quote!(
+        #[serde(default, rename = "readOnly", skip_serializing_if = "std::ops::Not::not")]
+        pub read_only: bool,
+    ));
+    // Schemas don't have `writeOnly` and `nullable` fields in v2, so these are emitted
+    // as extensions and only become proper fields in the v3 conversion.
+    gen.extend(quote!(
+        #[serde(default, rename = "x-writeOnly", skip_serializing_if = "std::ops::Not::not")]
+        pub write_only: bool,
+    ));
+    gen.extend(quote!(
+        #[serde(default, rename = "x-nullable", skip_serializing_if = "std::ops::Not::not")]
+        pub nullable: bool,
+    ));
+    gen.extend(quote!(
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        pub deprecated: bool,
+    ));
+    gen.extend(quote!(
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub maximum: Option<f32>,
+    ));
+    gen.extend(quote!(
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub minimum: Option<f32>,
+    ));
+    gen.extend(quote!(
+        #[serde(rename = "maxLength", skip_serializing_if = "Option::is_none")]
+        pub max_length: Option<u32>,
+    ));
+    gen.extend(quote!(
+        #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
+        pub min_length: Option<u32>,
+    ));
+    gen.extend(quote!(
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub pattern: Option<String>,
+    ));
+    gen.extend(quote!(
+        #[serde(rename = "maxItems", skip_serializing_if = "Option::is_none")]
+        pub max_items: Option<u32>,
+    ));
+    gen.extend(quote!(
+        #[serde(rename = "minItems", skip_serializing_if = "Option::is_none")]
+        pub min_items: Option<u32>,
+    ));
+    gen.extend(quote!(
+        #[serde(default, rename = "uniqueItems", skip_serializing_if = "std::ops::Not::not")]
+        pub unique_items: bool,
+    ));
+    gen.extend(quote!(
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub xml: Option<paperclip::v2::models::Xml>,
+    ));
+
+    gen.extend(quote!(
+        #[serde(
+            flatten,
+            skip_serializing_if = "std::collections::BTreeMap::is_empty",
+            deserialize_with = "paperclip::v2::schema::deserialize_extensions"
+        )]
+        pub extensions: std::collections::BTreeMap<String, serde_json::Value>,
+    ));
 
     gen.extend(quote!(
         #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
@@ -308,6 +379,27 @@ fn schema_fields(name: &Ident, is_ref: bool) -> proc_macro2::TokenStream {
         pub enum_: Vec<serde_json::Value>,
     ));
 
+    gen.extend(quote!(
+        #[serde(default, rename = "anyOf", skip_serializing_if = "Vec::is_empty")]
+        pub any_of: Vec<
+    ));
+    add_self(&mut gen);
+    gen.extend(quote!(>,));
+
+    gen.extend(quote!(
+        #[serde(default, rename = "oneOf", skip_serializing_if = "Vec::is_empty")]
+        pub one_of: Vec<
+    ));
+    add_self(&mut gen);
+    gen.extend(quote!(>,));
+
+    gen.extend(quote!(
+        #[serde(default, rename = "allOf", skip_serializing_if = "Vec::is_empty")]
+        pub all_of: Vec<
+    ));
+    add_self(&mut gen);
+    gen.extend(quote!(>,));
+
     gen.extend(quote!(
         #[serde(rename = "additionalProperties", skip_serializing_if = "Option::is_none")]
         pub extra_props: Option<paperclip::v2::models::Either<bool,
@@ -320,11 +412,39 @@ fn schema_fields(name: &Ident, is_ref: bool) -> proc_macro2::TokenStream {
         pub required: std::collections::BTreeSet<String>,
     ));
 
+    // Name of the example when it's shared between operations (in the v3 components).
+    gen.extend(quote!(
+        #[doc(hidden)]
+        #[serde(skip)]
+        pub example_ref: Option<String>,
+    ));
+
+    // Whether the (query) parameter generated from this property accepts empty values.
+    gen.extend(quote!(
+        #[doc(hidden)]
+        #[serde(skip)]
+        pub allow_empty_value: bool,
+    ));
+
+    // Serialization style of the (query) parameter generated from this property.
+    gen.extend(quote!(
+        #[doc(hidden)]
+        #[serde(skip)]
+        pub style: Option<paperclip::v2::models::ParameterStyle>,
+    ));
+
     if is_ref {
         gen.extend(quote!(
             #[serde(skip)]
             cyclic: bool,
         ));
+    } else {
+        // Marks `$ref` placeholders of recursive types, so that they survive `remove_refs`.
+        gen.extend(quote!(
+            #[doc(hidden)]
+            #[serde(skip)]
+            pub cyclic: bool,
+        ));
     }
 
     quote!({
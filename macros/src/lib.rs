@@ -60,6 +60,22 @@ pub fn api_v2_response(input: TokenStream) -> TokenStream {
     self::actix::emit_v2_definition(input, true)
 }
 
+/// Implements `Apiv2Schema` (through `TypedData`) for a type which can't derive it, for
+/// example because it's serialized by hand.
+///
+/// ```ignore
+/// impl_apiv2_schema!(Money => string);
+/// impl_apiv2_schema!(AccountId => string, format = "uuid");
+/// ```
+///
+/// **NOTE:** Rust's orphan rules still apply, so this only works for types defined in your crate.
+#[cfg(feature = "actix")]
+#[proc_macro_error]
+#[proc_macro]
+pub fn impl_apiv2_schema(input: TokenStream) -> TokenStream {
+    self::actix::emit_v2_schema_impl(input)
+}
+
 /// Marker attribute for indicating that an object forbids public access to operation (for example AccessToken).
 #[cfg(feature = "actix")]
 #[proc_macro_error]
@@ -78,6 +94,9 @@ pub fn api_v2_header(input: TokenStream) -> TokenStream {
 
 /// Marker attribute for indicating that the marked object can represent non-2xx (error)
 /// status codes with optional descriptions.
+///
+/// For enums, the codes can also be specified on the variants with
+/// `#[api_v2_errors_variant(code = 404, description = "...")]`.
 #[cfg(feature = "actix")]
 #[proc_macro_error]
 #[proc_macro_attribute]
@@ -85,6 +104,16 @@ pub fn api_v2_errors(attrs: TokenStream, input: TokenStream) -> TokenStream {
     self::actix::emit_v2_errors(attrs, input)
 }
 
+/// Derive attribute for implementing `Display` and actix-web's `ResponseError` for an error
+/// marked with `#[api_v2_errors]`, responding with the status code declared for each variant
+/// (`500 Internal Server Error` otherwise).
+#[cfg(feature = "actix")]
+#[proc_macro_error]
+#[proc_macro_derive(Apiv2ResponseError)]
+pub fn api_v2_response_error(input: TokenStream) -> TokenStream {
+    self::actix::emit_v2_response_error(input)
+}
+
 /// Marker attribute for indicating that the marked object can filter error responses from the
 /// the `#[api_v2_errors]` macro.
 #[cfg(feature = "actix")]
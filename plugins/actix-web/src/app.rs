@@ -20,7 +20,10 @@ use actix_web::{
     Error, HttpResponse,
 };
 use futures::future::{ok as fut_ok, Ready};
-use paperclip_core::v2::models::{DefaultApiRaw, SecurityScheme};
+use paperclip_core::v2::{
+    models::{DefaultApiRaw, DefaultParameterRaw, DefaultSchemaRaw, SecurityScheme},
+    OperationModifier,
+};
 #[cfg(feature = "rapidoc")]
 use tinytemplate::TinyTemplate;
 
@@ -38,6 +41,7 @@ pub struct App<T> {
     spec_v3: Option<Arc<RwLock<openapiv3::OpenAPI>>>,
     #[cfg(any(feature = "swagger-ui", feature = "rapidoc"))]
     spec_path: Option<String>,
+    default_error_schema: Option<DefaultSchemaRaw>,
     inner: Option<actix_web::App<T>>,
 }
 
@@ -65,6 +69,7 @@ impl<T> OpenApiExt<T> for actix_web::App<T> {
             spec_v3: None,
             #[cfg(any(feature = "swagger-ui", feature = "rapidoc"))]
             spec_path: None,
+            default_error_schema: None,
             inner: Some(self),
         }
     }
@@ -76,6 +81,7 @@ impl<T> OpenApiExt<T> for actix_web::App<T> {
             spec_v3: None,
             #[cfg(any(feature = "swagger-ui", feature = "rapidoc"))]
             spec_path: None,
+            default_error_schema: None,
             inner: Some(self),
         }
     }
@@ -130,6 +136,23 @@ where
         self
     }
 
+    #[cfg(feature = "oas31")]
+    /// Records the operation of the given route as an (OpenAPI 3.1) webhook with
+    /// the given name in the v3 spec.
+    ///
+    /// **NOTE:** Webhooks are requests made *by* the API, so the route is only used
+    /// for generating the spec and it's not mounted in the app.
+    pub fn webhook(self, name: &str, route: Route) -> Self {
+        let mut w = RouteWrapper::from(name, route);
+        {
+            let mut api = self.spec.write().unwrap();
+            api.definitions.extend(w.definitions());
+            SecurityScheme::append_map(w.security_definitions(), &mut api.security_definitions);
+            w.update_operations(&mut api.webhooks);
+        }
+        self
+    }
+
     /// Wrapper for [`actix_web::App::service`](https://docs.rs/actix-web/*/actix_web/struct.App.html#method.service).
     pub fn service<F>(mut self, mut factory: F) -> Self
     where
@@ -202,6 +225,7 @@ where
             spec_v3: self.spec_v3,
             #[cfg(any(feature = "swagger-ui", feature = "rapidoc"))]
             spec_path: None,
+            default_error_schema: self.default_error_schema,
             inner: self.inner.take().map(|a| a.wrap(mw)),
         }
     }
@@ -232,6 +256,7 @@ where
             spec_v3: self.spec_v3,
             #[cfg(any(feature = "swagger-ui", feature = "rapidoc"))]
             spec_path: None,
+            default_error_schema: self.default_error_schema,
             inner: self.inner.take().map(|a| a.wrap_fn(mw)),
         }
     }
@@ -441,6 +466,44 @@ where
         self
     }
 
+    /// Documents the schema of the given type for the error (`4xx`, `5xx` and `default`)
+    /// responses of all operations which don't have a schema of their own, so that
+    /// it doesn't have to be repeated (as `default_schema`) for every error type.
+    ///
+    /// **NOTE:** This applies to the routes added both before and after this call.
+    pub fn default_error_schema<S: OperationModifier>(mut self) -> Self {
+        {
+            let mut api = self.spec.write().unwrap();
+            S::update_definitions(&mut api.definitions);
+            let mut schema = S::schema_with_ref();
+            if schema.reference.is_some() {
+                schema = DefaultSchemaRaw {
+                    name: schema.name,
+                    reference: schema.reference,
+                    ..Default::default()
+                };
+            }
+            api.fill_error_schemas(&schema);
+            self.default_error_schema = Some(schema);
+        }
+        self
+    }
+
+    /// Adds a parameter shared by the operations (e.g., a common header) to the spec,
+    /// so that it's documented once (in `parameters` for v2 and `components.parameters`
+    /// for v3) and referenced by the operations declaring a parameter with the same
+    /// name and location. Only header, query and path parameters are referenced.
+    ///
+    /// **NOTE:** This applies to the routes added both before and after this call.
+    pub fn shared_parameter(self, key: &str, parameter: DefaultParameterRaw) -> Self {
+        {
+            let mut api = self.spec.write().unwrap();
+            api.parameters.insert(key.into(), parameter);
+            api.reference_shared_parameters();
+        }
+        self
+    }
+
     /// Updates the underlying spec with definitions and operations from the given factory.
     fn update_from_mountable<F>(&mut self, factory: &mut F)
     where
@@ -453,6 +516,10 @@ where
             &mut api.security_definitions,
         );
         factory.update_operations(&mut api.paths);
+        if let Some(schema) = &self.default_error_schema {
+            api.fill_error_schemas(schema);
+        }
+        api.reference_shared_parameters();
         if cfg!(feature = "normalize") {
             for map in api.paths.values_mut() {
                 map.normalize();
@@ -245,7 +245,9 @@ where
         if U::is_visible() {
             op.set_parameter_names_from_path_template(&self.path);
             for method in METHODS {
-                self.operations.insert(method.into(), op.clone());
+                let mut op = op.clone();
+                op.remove_body_for_method(method.into());
+                self.operations.insert(method.into(), op);
             }
 
             self.definitions.extend(U::definitions());
@@ -267,6 +269,7 @@ pub struct Scope<S = actix_web::Scope> {
     path_map: BTreeMap<String, DefaultPathItemRaw>,
     definitions: BTreeMap<String, DefaultSchemaRaw>,
     security: BTreeMap<String, SecurityScheme>,
+    tags: Vec<String>,
     inner: Option<S>,
 }
 
@@ -278,6 +281,7 @@ impl Scope {
             path_map: BTreeMap::new(),
             definitions: BTreeMap::new(),
             security: BTreeMap::new(),
+            tags: vec![],
             inner: Some(actix_web::Scope::new(path)),
         }
     }
@@ -321,6 +325,16 @@ where
         self
     }
 
+    /// Adds the given tags to all the operations mounted under this scope.
+    pub fn tags<I, U>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = U>,
+        U: Into<String>,
+    {
+        self.tags.extend(tags.into_iter().map(Into::into));
+        self
+    }
+
     /// Wrapper for [`actix_web::Scope::configure`](https://docs.rs/actix-web/*/actix_web/struct.Scope.html#method.configure).
     pub fn configure<F>(mut self, f: F) -> Self
     where
@@ -405,6 +419,7 @@ where
             path_map: self.path_map,
             definitions: self.definitions,
             security: self.security,
+            tags: self.tags,
             inner: self.inner.take().map(|s| s.wrap(mw)),
         }
     }
@@ -435,6 +450,7 @@ where
             path_map: self.path_map,
             definitions: self.definitions,
             security: self.security,
+            tags: self.tags,
             inner: self.inner.take().map(|s| s.wrap_fn(mw)),
         }
     }
@@ -487,7 +503,15 @@ impl<T> Mountable for Scope<T> {
     }
 
     fn update_operations(&mut self, map: &mut BTreeMap<String, DefaultPathItemRaw>) {
-        for (path, item) in mem::take(&mut self.path_map) {
+        for (path, mut item) in mem::take(&mut self.path_map) {
+            for op in item.methods.values_mut() {
+                for tag in &self.tags {
+                    if !op.tags.contains(tag) {
+                        op.tags.push(tag.clone());
+                    }
+                }
+            }
+
             let op_map = map.entry(path).or_default();
             op_map.methods.extend(item.methods);
         }
@@ -638,10 +662,13 @@ where
             op.set_parameter_names_from_path_template(path.as_ref());
 
             if let Some(meth) = route.method {
+                op.remove_body_for_method(meth);
                 operations.insert(meth, op);
             } else {
                 for method in METHODS {
-                    operations.insert(method.into(), op.clone());
+                    let mut op = op.clone();
+                    op.remove_body_for_method(method.into());
+                    operations.insert(method.into(), op);
                 }
             }
         }
@@ -14,20 +14,45 @@ pub mod v2;
 
 pub use error::{PaperClipError, PaperClipResult};
 pub use paperclip_core::util;
+#[cfg(feature = "v3")]
+pub use paperclip_core::v3;
 #[cfg(feature = "v2")]
 pub use paperclip_macros::api_v2_schema_struct as api_v2_schema;
 
 #[cfg(feature = "actix-base")]
 pub mod actix {
     //! Plugin types, traits and macros for actix-web framework.
+    //!
+    //! Unit structs deriving `Apiv2Schema` have an empty schema, which is warned about
+    //! (as a deprecation) unless they're marked with `#[openapi(empty)]`:
+    //!
+    //! ```compile_fail
+    //! #![deny(warnings)]
+    //! use paperclip::actix::Apiv2Schema;
+    //!
+    //! #[derive(Apiv2Schema)]
+    //! struct Marker;
+    //! # fn main() { let _ = Marker; }
+    //! ```
+    //!
+    //! ```
+    //! #![deny(warnings)]
+    //! use paperclip::actix::Apiv2Schema;
+    //!
+    //! #[derive(Apiv2Schema)]
+    //! #[openapi(empty = "only used for routing")]
+    //! struct Marker;
+    //! # fn main() { let _ = Marker; }
+    //! ```
 
     pub use paperclip_actix::{
-        api_v2_errors, api_v2_errors_overlay, api_v2_operation, delete, get, head, patch, post,
-        put, web, Apiv2Header, Apiv2Response, Apiv2Schema, Apiv2Security, App, Mountable,
-        OpenApiExt,
+        api_v2_errors, api_v2_errors_overlay, api_v2_operation, delete, get, head,
+        impl_apiv2_schema, patch, post, put, web, Apiv2Header, Apiv2Response, Apiv2ResponseError,
+        Apiv2Schema, Apiv2Security, App, Mountable, OpenApiExt,
     };
     pub use paperclip_core::v2::{
-        AcceptedJson, CreatedJson, NoContent, OperationModifier, ResponderWrapper, ResponseWrapper,
+        AcceptedJson, CreatedJson, NoContent, OperationModifier, ProblemDetails, ResponderWrapper,
+        ResponseWrapper,
     };
 
     #[cfg(feature = "actix4")]
@@ -10,9 +10,15 @@ extern crate actix_web_validator3 as actix_web_validator;
 
 #[cfg(feature = "actix3-validator")]
 extern crate validator12 as validator;
-#[cfg(feature = "actix4-validator")]
+#[cfg(any(
+    feature = "actix4-validator",
+    all(feature = "validator", not(feature = "actix3-validator"))
+))]
 extern crate validator14 as validator;
 
+#[cfg(feature = "actix-multipart")]
+extern crate actix_multipart07 as actix_multipart;
+
 #[cfg(not(feature = "actix4"))]
 extern crate actix_service1 as actix_service;
 #[cfg(feature = "actix4")]
@@ -50,7 +56,7 @@ use paperclip::{
         api_v2_errors, api_v2_errors_overlay, api_v2_operation, delete, get, patch, post, put, web,
         Apiv2Header, Apiv2Schema, Apiv2Security, CreatedJson, NoContent, OpenApiExt,
     },
-    v2::models::{DefaultApiRaw, Info, Tag},
+    v2::models::{DefaultApiRaw, Info, Tag, TagGroup},
 };
 use std::sync::Mutex;
 #[cfg(any(feature = "actix3-validator", feature = "actix4-validator"))]
@@ -1269,6 +1275,7 @@ fn test_params() {
                                 }
                             },
                             "post": {
+                                "consumes": ["application/x-www-form-urlencoded"],
                                 "parameters": [
                                     {
                                         "format": "int32",
@@ -1923,7 +1930,7 @@ fn test_serde_flatten() {
                         .route(web::get().to(some_images))
                         .route(web::post().to(add_images)),
                 )
-                .service(web::resource("/article").route(web::get().to(add_article)))
+                .service(web::resource("/article").route(web::post().to(add_article)))
                 .build()
         },
         |addr| {
@@ -2049,7 +2056,7 @@ fn test_serde_flatten() {
                       },
                       "paths": {
                         "/article": {
-                          "get": {
+                          "post": {
                             "parameters": [
                               {
                                 "in": "body",
@@ -2372,16 +2379,19 @@ fn test_tags() {
                     name: "Dogs".to_string(),
                     description: Some("Images of dogs".to_string()),
                     external_docs: None,
+                    ..Default::default()
                 },
                 Tag {
                     name: "Cats".to_string(),
                     description: Some("Images of cats".to_string()),
                     external_docs: None,
+                    ..Default::default()
                 },
                 Tag {
                     name: "Nice cars".to_string(),
                     description: Some("Images of nice cars".to_string()),
                     external_docs: None,
+                    ..Default::default()
                 },
             ];
             let mut extensions = BTreeMap::new();
@@ -4765,6 +4775,76 @@ fn test_rename() {
     );
 }
 
+#[test]
+fn test_field_rename() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[serde(rename_all = "camelCase")]
+    struct Pet {
+        #[serde(rename = "petName")]
+        #[openapi(rename = "name")]
+        pet_name: String,
+        #[openapi(rename = "age")]
+        age_in_years: u8,
+        owner_id: u64,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Pet::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "age": {
+                    "format": "int32",
+                    "type": "integer"
+                },
+                "name": {
+                    "type": "string"
+                },
+                "ownerId": {
+                    "format": "int64",
+                    "type": "integer"
+                }
+            },
+            "required": ["age", "name", "ownerId"],
+            "type": "object"
+        })
+    );
+}
+
+#[test]
+fn test_field_description() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Order {
+        /// Internal ledger reference, see `Ledger::lookup`.
+        #[openapi(description = "Unique order reference.")]
+        reference: String,
+        /// Total amount in cents.
+        amount: u64,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Order::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "amount": {
+                    "description": "Total amount in cents.",
+                    "format": "int64",
+                    "type": "integer"
+                },
+                "reference": {
+                    "description": "Unique order reference.",
+                    "type": "string"
+                }
+            },
+            "required": ["amount", "reference"],
+            "type": "object"
+        })
+    );
+}
+
 #[test]
 fn test_example() {
     #[derive(Deserialize, Serialize, Apiv2Schema)]
@@ -5171,6 +5251,26 @@ fn test_schema_with_generics() {
     );
 }
 
+#[test]
+fn test_schema_with_const_generics() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Arr<const N: usize> {
+        items: Vec<u8>,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Grid<T, const W: usize> {
+        cells: Vec<T>,
+    }
+
+    assert_eq!(Arr::<3>::name(), Some("Arr<3>".into()));
+    assert_eq!(Arr::<4>::name(), Some("Arr<4>".into()));
+    assert_eq!(Grid::<bool, 8>::name(), Some("Grid<8>".into()));
+    assert_eq!(Grid::<Arr<2>, 8>::name(), Some("Grid<Arr<2>, 8>".into()));
+}
+
 #[test]
 #[cfg(feature = "path-in-definition")]
 fn test_module_path_in_definition_name() {
@@ -5487,3 +5587,4762 @@ fn test_wrap() {
         },
     );
 }
+
+#[test]
+fn test_adjacently_tagged_enum() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Foo {
+        bar: String,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[serde(tag = "t", content = "c")]
+    enum E {
+        A(Foo),
+        B(Foo, u8),
+        C,
+    }
+
+    assert_eq!(
+        serde_json::to_value(E::raw_schema()).unwrap(),
+        json!({
+            "anyOf": [
+                {
+                    "properties": {
+                        "c": {
+                            "properties": {
+                                "bar": {
+                                    "type": "string"
+                                }
+                            },
+                            "required": ["bar"],
+                            "type": "object"
+                        },
+                        "t": {
+                            "enum": ["A"],
+                            "type": "string"
+                        }
+                    },
+                    "required": ["c", "t"],
+                    "type": "object"
+                },
+                {
+                    "properties": {
+                        "c": {
+                            "properties": {
+                                "0": {
+                                    "properties": {
+                                        "bar": {
+                                            "type": "string"
+                                        }
+                                    },
+                                    "required": ["bar"],
+                                    "type": "object"
+                                },
+                                "1": {
+                                    "format": "int32",
+                                    "type": "integer"
+                                }
+                            },
+                            "required": ["0", "1"],
+                            "type": "object"
+                        },
+                        "t": {
+                            "enum": ["B"],
+                            "type": "string"
+                        }
+                    },
+                    "required": ["c", "t"],
+                    "type": "object"
+                },
+                {
+                    "properties": {
+                        "t": {
+                            "enum": ["C"],
+                            "type": "string"
+                        }
+                    },
+                    "required": ["t"],
+                    "type": "object"
+                }
+            ]
+        })
+    );
+}
+
+#[test]
+fn test_renamed_externally_tagged_enum() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[serde(rename_all = "snake_case")]
+    enum Shape {
+        RoundCircle {
+            radius: f64,
+        },
+        #[serde(rename = "sq")]
+        Square(f64),
+        Point,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Shape::raw_schema()).unwrap(),
+        json!({
+            "anyOf": [
+                {
+                    "enum": ["point"],
+                    "type": "string"
+                },
+                {
+                    "properties": {
+                        "round_circle": {
+                            "properties": {
+                                "radius": {
+                                    "format": "double",
+                                    "type": "number"
+                                }
+                            },
+                            "required": ["radius"],
+                            "type": "object"
+                        }
+                    },
+                    "required": ["round_circle"],
+                    "type": "object"
+                },
+                {
+                    "properties": {
+                        "sq": {
+                            "format": "double",
+                            "type": "number"
+                        }
+                    },
+                    "required": ["sq"],
+                    "type": "object"
+                }
+            ]
+        })
+    );
+}
+
+#[test]
+fn test_string_wrappers() {
+    use paperclip::v2::schema::Apiv2Schema;
+    use std::borrow::Cow;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Label<'a> {
+        name: Cow<'static, str>,
+        alias: Option<Cow<'a, str>>,
+        note: Box<str>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Label::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "alias": {
+                    "type": "string"
+                },
+                "name": {
+                    "type": "string"
+                },
+                "note": {
+                    "type": "string"
+                }
+            },
+            "required": ["name", "note"],
+            "type": "object"
+        })
+    );
+}
+
+#[test]
+fn test_serde_skip_one_way() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Account {
+        name: String,
+        #[serde(skip_serializing)]
+        password: String,
+        #[serde(skip_deserializing)]
+        id: u64,
+        #[openapi(skip_deserializing)]
+        created_at: String,
+        #[serde(skip_serializing, skip_deserializing)]
+        cache: String,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Account::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "created_at": {
+                    "readOnly": true,
+                    "type": "string"
+                },
+                "id": {
+                    "format": "int64",
+                    "readOnly": true,
+                    "type": "integer"
+                },
+                "name": {
+                    "type": "string"
+                },
+                "password": {
+                    "type": "string",
+                    "x-writeOnly": true
+                }
+            },
+            "required": ["created_at", "id", "name"],
+            "type": "object"
+        })
+    );
+}
+
+#[test]
+fn test_password_format_write_only() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Credentials {
+        username: String,
+        #[openapi(format = "password")]
+        password: String,
+        #[openapi(format = "password", write_only = false)]
+        api_key: String,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Credentials::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "api_key": {
+                    "format": "password",
+                    "type": "string"
+                },
+                "password": {
+                    "format": "password",
+                    "type": "string",
+                    "x-writeOnly": true
+                },
+                "username": {
+                    "type": "string"
+                }
+            },
+            "required": ["api_key", "password", "username"],
+            "type": "object"
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_operation_explicit_response() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Greeting {
+        message: String,
+    }
+
+    #[api_v2_operation(response = "Greeting")]
+    async fn greet() -> actix_web::HttpResponse {
+        actix_web::HttpResponse::Ok().json(Greeting {
+            message: "Hi there!".into(),
+        })
+    }
+
+    let op = paperclip_greet::operation();
+    assert_eq!(
+        serde_json::to_value(&op.responses).unwrap(),
+        json!({
+            "200": {
+                "description": "OK",
+                "schema": {
+                    "$ref": "#/definitions/Greeting"
+                }
+            }
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(paperclip_greet::definitions()).unwrap(),
+        json!({
+            "Greeting": {
+                "properties": {
+                    "message": {
+                        "type": "string"
+                    }
+                },
+                "required": ["message"],
+                "type": "object"
+            }
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_operation_multiple_success_responses() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Job {
+        id: u64,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct JobTicket {
+        ticket: String,
+    }
+
+    #[api_v2_operation(responses(200 = "Job", 202 = "JobTicket"))]
+    async fn run_job() -> actix_web::HttpResponse {
+        actix_web::HttpResponse::Accepted().json(JobTicket {
+            ticket: "abc".into(),
+        })
+    }
+
+    let op = paperclip_run_job::operation();
+    assert_eq!(
+        serde_json::to_value(&op.responses).unwrap(),
+        json!({
+            "200": {
+                "description": "OK",
+                "schema": {
+                    "$ref": "#/definitions/Job"
+                }
+            },
+            "202": {
+                "description": "Accepted",
+                "schema": {
+                    "$ref": "#/definitions/JobTicket"
+                }
+            }
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(paperclip_run_job::definitions()).unwrap(),
+        json!({
+            "Job": {
+                "properties": {
+                    "id": {
+                        "format": "int64",
+                        "type": "integer"
+                    }
+                },
+                "required": ["id"],
+                "type": "object"
+            },
+            "JobTicket": {
+                "properties": {
+                    "ticket": {
+                        "type": "string"
+                    }
+                },
+                "required": ["ticket"],
+                "type": "object"
+            }
+        })
+    );
+}
+
+#[test]
+fn test_tag_groups() {
+    let mut spec = DefaultApiRaw {
+        tags: vec![
+            Tag {
+                name: "Cats".into(),
+                ..Default::default()
+            },
+            Tag {
+                name: "Dogs".into(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    spec.set_tag_groups(vec![
+        TagGroup::new("Pets", vec!["Cats", "Dogs"]),
+        TagGroup::new("Misc", Vec::<String>::new()),
+    ]);
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(
+        json["x-tagGroups"],
+        json!([
+            {
+                "name": "Pets",
+                "tags": ["Cats", "Dogs"]
+            },
+            {
+                "name": "Misc",
+                "tags": []
+            }
+        ])
+    );
+
+    spec.set_tag_groups(None);
+    assert!(!spec.extensions.contains_key("x-tagGroups"));
+}
+
+#[test]
+fn test_deprecated_tag() {
+    use paperclip::v2::models::{DefaultOperationRaw, DefaultPathItemRaw, HttpMethod};
+
+    let mut spec = DefaultApiRaw {
+        tags: vec![
+            Tag {
+                name: "Legacy".into(),
+                deprecated: true,
+                ..Default::default()
+            },
+            Tag {
+                name: "Pets".into(),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let mut item = DefaultPathItemRaw::default();
+    for (method, tag) in [(HttpMethod::Get, "Pets"), (HttpMethod::Delete, "Legacy")] {
+        item.methods.insert(
+            method,
+            DefaultOperationRaw {
+                tags: vec![tag.into()],
+                ..Default::default()
+            },
+        );
+    }
+    spec.paths.insert("/pets".into(), item);
+    spec.deprecate_tagged_operations();
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(
+        json["tags"],
+        json!([
+            {
+                "name": "Legacy",
+                "x-tag-deprecated": true
+            },
+            {
+                "name": "Pets"
+            }
+        ])
+    );
+    assert_eq!(json["paths"]["/pets"]["delete"]["deprecated"], true);
+    assert!(json["paths"]["/pets"]["get"].get("deprecated").is_none());
+}
+
+#[test]
+#[cfg(feature = "option-nullable")]
+fn test_option_nullable() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Profile {
+        name: String,
+        nickname: Option<String>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Profile::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "name": {
+                    "type": "string"
+                },
+                "nickname": {
+                    "x-nullable": true,
+                    "type": "string"
+                }
+            },
+            "required": ["name"],
+            "type": "object"
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "option-nullable")]
+fn test_option_nullable_enum() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Pet {
+        color: Color,
+        collar_color: Option<Color>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Pet::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "collar_color": {
+                    "enum": ["Red", "Green", null],
+                    "x-nullable": true,
+                    "type": "string"
+                },
+                "color": {
+                    "enum": ["Red", "Green"],
+                    "type": "string"
+                }
+            },
+            "required": ["color"],
+            "type": "object"
+        })
+    );
+}
+
+#[test]
+fn test_newtype_example() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    /// Where it all began.
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[openapi(example = r#"{ "x": 0, "y": 0 }"#)]
+    struct Origin(Point);
+
+    let schema = Origin::raw_schema();
+    assert_eq!(
+        serde_json::to_value(&schema).unwrap(),
+        json!({
+            "description": "Where it all began.",
+            "example": {
+                "x": 0,
+                "y": 0
+            },
+            "properties": {
+                "x": {
+                    "format": "int32",
+                    "type": "integer"
+                },
+                "y": {
+                    "format": "int32",
+                    "type": "integer"
+                }
+            },
+            "required": ["x", "y"],
+            "type": "object"
+        })
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let v3: openapiv3::ReferenceOr<openapiv3::Schema> = schema.into();
+        match v3 {
+            openapiv3::ReferenceOr::Item(s) => {
+                assert_eq!(s.schema_data.example, Some(json!({ "x": 0, "y": 0 })));
+                assert_eq!(
+                    s.schema_data.description.as_deref(),
+                    Some("Where it all began.")
+                );
+            }
+            openapiv3::ReferenceOr::Reference { .. } => panic!("expected inline schema"),
+        }
+    }
+}
+
+#[test]
+fn test_openapi_flatten() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Audit {
+        created_by: String,
+        updated_by: Option<String>,
+    }
+
+    #[derive(Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Document {
+        title: String,
+        #[openapi(flatten)]
+        audit: Audit,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Document::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "created_by": {
+                    "type": "string"
+                },
+                "title": {
+                    "type": "string"
+                },
+                "updated_by": {
+                    "type": "string"
+                }
+            },
+            "required": ["created_by", "title"],
+            "type": "object"
+        })
+    );
+}
+
+#[test]
+fn test_recursive_schema() {
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Node {
+        name: String,
+        children: Vec<Node>,
+    }
+
+    #[api_v2_operation]
+    async fn get_tree() -> web::Json<Node> {
+        web::Json(Node {
+            name: "root".into(),
+            children: vec![],
+        })
+    }
+
+    run_and_check_app(
+        || {
+            App::new()
+                .wrap_api()
+                .with_json_spec_at("/api/spec")
+                .service(web::resource("/tree").route(web::get().to(get_tree)))
+                .build()
+        },
+        |addr| {
+            let resp = CLIENT
+                .get(&format!("http://{}/api/spec", addr))
+                .send()
+                .expect("request failed?");
+
+            check_json(
+                resp,
+                json!({
+                  "definitions": {
+                    "Node": {
+                      "properties": {
+                        "children": {
+                          "items": {
+                            "$ref": "#/definitions/Node"
+                          },
+                          "type": "array"
+                        },
+                        "name": {
+                          "type": "string"
+                        }
+                      },
+                      "required": ["children", "name"],
+                      "type": "object"
+                    }
+                  },
+                  "info": {
+                    "title": "",
+                    "version": ""
+                  },
+                  "paths": {
+                    "/tree": {
+                      "get": {
+                        "responses": {
+                          "200": {
+                            "description": "OK",
+                            "schema": {
+                              "$ref": "#/definitions/Node"
+                            }
+                          }
+                        }
+                      }
+                    }
+                  },
+                  "swagger": "2.0"
+                }),
+            );
+        },
+    );
+}
+
+#[test]
+fn test_impl_apiv2_schema() {
+    use paperclip::{actix::impl_apiv2_schema, v2::schema::Apiv2Schema};
+
+    // Stand-ins for types which can't derive the schema.
+    #[derive(Deserialize, Serialize)]
+    struct Money(i64);
+
+    #[derive(Deserialize, Serialize)]
+    struct AccountId(String);
+
+    impl_apiv2_schema!(Money => string);
+    impl_apiv2_schema!(AccountId => string, format = "uuid");
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Account {
+        id: AccountId,
+        balance: Money,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Account::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "balance": {
+                    "type": "string"
+                },
+                "id": {
+                    "format": "uuid",
+                    "type": "string"
+                }
+            },
+            "required": ["balance", "id"],
+            "type": "object"
+        })
+    );
+}
+
+#[test]
+fn test_schema_extensions() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[openapi(extension("x-vendor-id" = "123", "x-internal" = true))]
+    struct Invoice {
+        #[openapi(extension("x-precision" = 2))]
+        amount: f64,
+    }
+
+    let schema = Invoice::raw_schema();
+    assert_eq!(
+        serde_json::to_value(&schema).unwrap(),
+        json!({
+            "properties": {
+                "amount": {
+                    "format": "double",
+                    "type": "number",
+                    "x-precision": 2
+                }
+            },
+            "required": ["amount"],
+            "type": "object",
+            "x-internal": true,
+            "x-vendor-id": "123"
+        })
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let v3: openapiv3::ReferenceOr<openapiv3::Schema> = schema.into();
+        match v3 {
+            openapiv3::ReferenceOr::Item(s) => {
+                assert_eq!(
+                    s.schema_data.extensions.get("x-vendor-id"),
+                    Some(&json!("123"))
+                );
+                assert_eq!(
+                    s.schema_data.extensions.get("x-internal"),
+                    Some(&json!(true))
+                );
+            }
+            openapiv3::ReferenceOr::Reference { .. } => panic!("expected inline schema"),
+        }
+    }
+}
+
+#[test]
+fn test_operation_extensions() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[api_v2_operation(extension("x-internal" = true, "x-rate-limit" = 10))]
+    async fn purge_cache() -> NoContent {
+        NoContent
+    }
+
+    let op = paperclip_purge_cache::operation();
+    assert_eq!(
+        serde_json::to_value(&op.extensions).unwrap(),
+        json!({
+            "x-internal": true,
+            "x-rate-limit": 10
+        })
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let op: openapiv3::Operation = op.into();
+        assert_eq!(op.extensions.get("x-internal"), Some(&json!(true)));
+        assert_eq!(op.extensions.get("x-rate-limit"), Some(&json!(10)));
+    }
+}
+
+#[test]
+fn test_operation_code_samples() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    /// Purge the cache
+    ///
+    /// Drops all cached entries.
+    ///
+    /// ```bash
+    /// curl -X DELETE http://localhost:8080/cache
+    /// ```
+    ///
+    /// Entries are rebuilt lazily.
+    #[api_v2_operation]
+    async fn purge_cache() -> NoContent {
+        NoContent
+    }
+
+    let op = paperclip_purge_cache::operation();
+    assert_eq!(op.summary.as_deref(), Some("Purge the cache"));
+    assert_eq!(
+        op.description.as_deref(),
+        Some("Drops all cached entries.\n\n Entries are rebuilt lazily.")
+    );
+    assert_eq!(
+        serde_json::to_value(&op.extensions).unwrap(),
+        json!({
+            "x-codeSamples": [
+                {
+                    "lang": "bash",
+                    "source": "curl -X DELETE http://localhost:8080/cache"
+                }
+            ]
+        })
+    );
+}
+
+#[test]
+fn test_untagged_enum_one_of() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct ById {
+        id: u64,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct ByName {
+        name: String,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[serde(untagged)]
+    #[openapi(one_of)]
+    enum Lookup {
+        Id(ById),
+        Name(ByName),
+    }
+
+    let schema = Lookup::raw_schema();
+    assert_eq!(
+        serde_json::to_value(&schema).unwrap(),
+        json!({
+            "oneOf": [
+                {
+                    "properties": {
+                        "id": {
+                            "format": "int64",
+                            "type": "integer"
+                        }
+                    },
+                    "required": ["id"],
+                    "type": "object"
+                },
+                {
+                    "properties": {
+                        "name": {
+                            "type": "string"
+                        }
+                    },
+                    "required": ["name"],
+                    "type": "object"
+                }
+            ]
+        })
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let v3: openapiv3::ReferenceOr<openapiv3::Schema> = schema.into();
+        match v3 {
+            openapiv3::ReferenceOr::Item(s) => match s.schema_kind {
+                openapiv3::SchemaKind::OneOf { one_of } => assert_eq!(one_of.len(), 2),
+                _ => panic!("expected oneOf"),
+            },
+            openapiv3::ReferenceOr::Reference { .. } => panic!("expected inline schema"),
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "v3")]
+fn test_all_of_metadata_v3() {
+    use paperclip::v2::models::DefaultSchemaRaw;
+
+    let wrapper = DefaultSchemaRaw {
+        title: Some("Labelled pet".into()),
+        description: Some("Pet along with its label.".into()),
+        example: Some(json!({ "name": "Rex" })),
+        all_of: vec![Box::new(DefaultSchemaRaw {
+            reference: Some("#/definitions/Pet".into()),
+            ..Default::default()
+        })],
+        ..Default::default()
+    };
+
+    let v3: openapiv3::ReferenceOr<openapiv3::Schema> = wrapper.into();
+    match v3 {
+        openapiv3::ReferenceOr::Item(s) => {
+            assert_eq!(s.schema_data.title.as_deref(), Some("Labelled pet"));
+            assert_eq!(
+                s.schema_data.description.as_deref(),
+                Some("Pet along with its label.")
+            );
+            assert_eq!(s.schema_data.example, Some(json!({ "name": "Rex" })));
+            match s.schema_kind {
+                openapiv3::SchemaKind::AllOf { all_of } => assert_eq!(all_of.len(), 1),
+                _ => panic!("expected allOf"),
+            }
+        }
+        openapiv3::ReferenceOr::Reference { .. } => panic!("expected inline schema"),
+    }
+}
+
+#[test]
+fn test_custom_number_formats() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Ledger {
+        #[openapi(format = "decimal")]
+        balance: f64,
+        #[openapi(format = "int128")]
+        counter: i64,
+    }
+
+    let schema = Ledger::raw_schema();
+    assert_eq!(
+        serde_json::to_value(&schema).unwrap(),
+        json!({
+            "properties": {
+                "balance": {
+                    "format": "decimal",
+                    "type": "number"
+                },
+                "counter": {
+                    "format": "int128",
+                    "type": "integer"
+                }
+            },
+            "required": ["balance", "counter"],
+            "type": "object"
+        })
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let balance: openapiv3::ReferenceOr<openapiv3::Schema> =
+            (*schema.properties["balance"].clone()).into();
+        match balance {
+            openapiv3::ReferenceOr::Item(s) => match s.schema_kind {
+                openapiv3::SchemaKind::Type(openapiv3::Type::Number(n)) => assert_eq!(
+                    n.format,
+                    openapiv3::VariantOrUnknownOrEmpty::Unknown("decimal".into())
+                ),
+                _ => panic!("expected number"),
+            },
+            openapiv3::ReferenceOr::Reference { .. } => panic!("expected inline schema"),
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_query_flattened_params() {
+    use paperclip::v2::{
+        models::{DataType, DefaultSchemaRaw, Either},
+        schema::{Apiv2Operation, Apiv2Schema},
+    };
+
+    #[derive(Deserialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Pagination {
+        /// Page cursor
+        cursor: String,
+        /// Page size
+        page_size: Option<u32>,
+    }
+
+    #[derive(Deserialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    struct SearchQuery {
+        term: String,
+        #[serde(flatten)]
+        pagination: Pagination,
+    }
+
+    /// A query struct which documents its pagination through `allOf`.
+    #[derive(Deserialize)]
+    struct ComposedQuery {}
+
+    impl Apiv2Schema for ComposedQuery {
+        fn raw_schema() -> DefaultSchemaRaw {
+            let mut schema = DefaultSchemaRaw {
+                data_type: Some(DataType::Object),
+                ..Default::default()
+            };
+            schema
+                .properties
+                .insert("term".into(), String::raw_schema().into());
+            schema.required.insert("term".into());
+            schema.all_of.push(Pagination::raw_schema().into());
+            schema
+        }
+    }
+
+    /// Same as `ComposedQuery`, but references the pagination definition.
+    #[derive(Deserialize)]
+    struct ComposedRefQuery {}
+
+    impl Apiv2Schema for ComposedRefQuery {
+        fn raw_schema() -> DefaultSchemaRaw {
+            let mut schema = DefaultSchemaRaw {
+                data_type: Some(DataType::Object),
+                ..Default::default()
+            };
+            schema
+                .properties
+                .insert("term".into(), String::raw_schema().into());
+            schema.required.insert("term".into());
+            schema.all_of.push(Pagination::schema_with_ref().into());
+            schema
+        }
+    }
+
+    #[api_v2_operation]
+    async fn search(_q: web::Query<SearchQuery>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation]
+    async fn composed(_q: web::Query<ComposedQuery>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation]
+    async fn composed_ref(_q: web::Query<ComposedRefQuery>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    for op in [
+        paperclip_search::operation(),
+        paperclip_composed::operation(),
+        paperclip_composed_ref::operation(),
+    ] {
+        let params = op
+            .parameters
+            .iter()
+            .map(|p| match p {
+                Either::Right(p) => (p.name.as_str(), p.required),
+                Either::Left(_) => panic!("expected inline parameter"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            params,
+            vec![("cursor", true), ("page_size", false), ("term", true)]
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_raw_operation() {
+    /// List all the pets
+    ///
+    /// Pets are returned in no particular order.
+    #[api_v2_operation]
+    async fn list_pets() -> Result<web::Json<Vec<Pet>>, Error> {
+        unimplemented!()
+    }
+
+    let op = paperclip_list_pets::raw_operation();
+    assert_eq!(op.summary.as_deref(), Some("List all the pets"));
+    assert_eq!(
+        op.description.as_deref(),
+        Some("Pets are returned in no particular order.")
+    );
+    assert!(op.responses.contains_key("200"));
+}
+
+#[test]
+#[cfg(feature = "validator")]
+fn test_validator_constraints() {
+    use paperclip::v2::schema::Apiv2Schema;
+    use validator::Validate;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema, Validate)]
+    struct Review {
+        #[validate(range(min = 1, max = 5))]
+        rating: u8,
+        #[validate(range(min = 0.5))]
+        weight: Option<f64>,
+        #[validate(length(min = 3, max = 120))]
+        title: String,
+        #[validate(length(equal = 2))]
+        country: String,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Review::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "country": {
+                    "maxLength": 2,
+                    "minLength": 2,
+                    "type": "string"
+                },
+                "rating": {
+                    "format": "int32",
+                    "maximum": 5.0,
+                    "minimum": 1.0,
+                    "type": "integer"
+                },
+                "title": {
+                    "maxLength": 120,
+                    "minLength": 3,
+                    "type": "string"
+                },
+                "weight": {
+                    "format": "double",
+                    "minimum": 0.5,
+                    "type": "number"
+                }
+            },
+            "required": ["country", "rating", "title"],
+            "type": "object"
+        })
+    );
+
+    // The constraints are kept in v3.
+    #[cfg(feature = "v3")]
+    {
+        let schema: openapiv3::ReferenceOr<openapiv3::Schema> = Review::raw_schema().into();
+        let schema = serde_json::to_value(schema).unwrap();
+        assert_eq!(
+            schema["properties"]["rating"],
+            json!({
+                "format": "int32",
+                "maximum": 5,
+                "minimum": 1,
+                "type": "integer"
+            })
+        );
+        assert_eq!(
+            schema["properties"]["weight"],
+            json!({
+                "format": "double",
+                "minimum": 0.5,
+                "type": "number"
+            })
+        );
+        assert_eq!(schema["properties"]["title"]["minLength"], json!(3));
+    }
+}
+
+#[test]
+fn test_int_enum() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Apiv2Schema)]
+    #[openapi(repr = "int")]
+    #[allow(dead_code)]
+    #[repr(i8)]
+    enum Status {
+        Ok = 0,
+        Retry,
+        Failed = 10,
+        Unknown = -1,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Status::raw_schema()).unwrap(),
+        json!({
+            "enum": [0, 1, 10, -1],
+            "type": "integer"
+        })
+    );
+}
+
+#[test]
+fn test_repr_int_enum() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    // Serde serializes variant names by default, even for `repr` enums.
+    #[derive(Serialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    #[repr(u8)]
+    enum Priority {
+        Low = 1,
+        Medium = 5,
+        High = 10,
+    }
+
+    // Non-literal discriminants don't prevent deriving the schema.
+    #[derive(Serialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    #[repr(u32)]
+    enum Limit {
+        Small = 1 << 4,
+        Large = 1 << 8,
+    }
+
+    // Strings can also be requested explicitly.
+    #[derive(Serialize, Apiv2Schema)]
+    #[openapi(repr = "string")]
+    #[allow(dead_code)]
+    #[repr(u8)]
+    enum Level {
+        Debug = 1,
+        Info = 2,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Priority::raw_schema()).unwrap(),
+        json!({
+            "enum": ["Low", "Medium", "High"],
+            "type": "string"
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(Limit::raw_schema()).unwrap(),
+        json!({
+            "enum": ["Small", "Large"],
+            "type": "string"
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(Level::raw_schema()).unwrap(),
+        json!({
+            "enum": ["Debug", "Info"],
+            "type": "string"
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "v3")]
+fn test_error_schema_reference_v3() {
+    use paperclip::v2::models::{DefaultOperationRaw, DefaultSchemaRaw, Either, Response};
+
+    let mut op = DefaultOperationRaw::default();
+    op.responses.insert(
+        "404".into(),
+        Either::Right(Response {
+            description: Some("Not found".into()),
+            schema: Some(DefaultSchemaRaw {
+                reference: Some("#/definitions/missing_definitions".into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+    );
+
+    let op = openapiv3::Operation::from(op);
+    let response = match op
+        .responses
+        .responses
+        .get(&openapiv3::StatusCode::Code(404))
+    {
+        Some(openapiv3::ReferenceOr::Item(response)) => response,
+        _ => panic!("expected inline response"),
+    };
+    match &response.content["application/json"].schema {
+        Some(openapiv3::ReferenceOr::Reference { reference }) => {
+            assert_eq!(reference, "#/components/schemas/missing_definitions")
+        }
+        _ => panic!("expected schema reference"),
+    }
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_either_response() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(Serialize, Apiv2Schema)]
+    struct Cat {
+        name: String,
+    }
+
+    #[derive(Serialize, Apiv2Schema)]
+    struct Dog {
+        name: String,
+    }
+
+    #[api_v2_operation]
+    async fn get_pet() -> actix_web::Either<web::Json<Cat>, web::Json<Dog>> {
+        unimplemented!()
+    }
+
+    let op = paperclip_get_pet::operation();
+    assert_eq!(
+        serde_json::to_value(&op.responses).unwrap(),
+        json!({
+            "200": {
+                "description": "OK",
+                "schema": {
+                    "oneOf": [
+                        { "$ref": "#/definitions/Cat" },
+                        { "$ref": "#/definitions/Dog" }
+                    ]
+                }
+            }
+        })
+    );
+
+    let definitions = paperclip_get_pet::definitions();
+    assert!(definitions.contains_key("Cat"));
+    assert!(definitions.contains_key("Dog"));
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_operation_stream_item() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(Serialize, Apiv2Schema)]
+    struct Event {
+        kind: String,
+    }
+
+    #[api_v2_operation(stream_item = "Event")]
+    async fn events() -> actix_web::HttpResponse {
+        unimplemented!()
+    }
+
+    let op = paperclip_events::operation();
+    assert_eq!(
+        serde_json::to_value(&op).unwrap(),
+        json!({
+            "produces": ["text/event-stream"],
+            "responses": {
+                "200": {
+                    "description": "OK",
+                    "schema": { "$ref": "#/definitions/Event" }
+                }
+            },
+            "x-streaming": true
+        })
+    );
+    assert!(paperclip_events::definitions().contains_key("Event"));
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_operation_no_content() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[api_v2_operation(no_content)]
+    async fn delete_pet() -> web::Json<()> {
+        unimplemented!()
+    }
+
+    assert_eq!(
+        serde_json::to_value(paperclip_delete_pet::operation().responses).unwrap(),
+        json!({
+            "204": {
+                "description": "No Content"
+            }
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_internal_operation() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[api_v2_operation(internal, summary = "Rebuild the search index")]
+    async fn reindex() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation]
+    async fn search() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    assert_eq!(
+        paperclip_reindex::is_visible(),
+        cfg!(feature = "internal-operations")
+    );
+    assert!(paperclip_search::is_visible());
+    assert_eq!(
+        paperclip_reindex::operation().summary.as_deref(),
+        Some("Rebuild the search index")
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_operation_preview_gate() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[api_v2_operation(gated = "new-billing")]
+    async fn invoices() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let op = paperclip_invoices::operation();
+    assert_eq!(
+        op.extensions.get("x_fp_preview_gate"),
+        Some(&json!("new-billing"))
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_operation_security_requirements() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[api_v2_operation(security(all("ApiKey", "Signature")))]
+    async fn transfer() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation(security(any("ApiKey", "OAuth")))]
+    async fn balance() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    assert_eq!(
+        serde_json::to_value(paperclip_transfer::operation().security).unwrap(),
+        json!([{ "ApiKey": [], "Signature": [] }])
+    );
+    assert_eq!(
+        serde_json::to_value(paperclip_balance::operation().security).unwrap(),
+        json!([{ "ApiKey": [] }, { "OAuth": [] }])
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let security = openapiv3::Operation::from(paperclip_transfer::operation())
+            .security
+            .expect("security requirements");
+        assert_eq!(security.len(), 1);
+        assert!(security[0].contains_key("ApiKey") && security[0].contains_key("Signature"));
+
+        let security = openapiv3::Operation::from(paperclip_balance::operation())
+            .security
+            .expect("security requirements");
+        assert_eq!(security.len(), 2);
+    }
+}
+
+#[test]
+fn test_field_enum_values() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Shipment {
+        #[openapi(enum_values("ground", "air", "sea"))]
+        method: String,
+        #[openapi(enum_values(1, 2, 3))]
+        priority: Option<u8>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Shipment::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "method": {
+                    "enum": ["ground", "air", "sea"],
+                    "type": "string"
+                },
+                "priority": {
+                    "enum": [1, 2, 3],
+                    "format": "int32",
+                    "type": "integer"
+                }
+            },
+            "required": ["method"],
+            "type": "object"
+        })
+    );
+}
+
+#[test]
+#[cfg(all(feature = "actix4", feature = "v3"))]
+fn test_shared_example_v3() {
+    use paperclip::v2::{
+        models::{DefaultApiRaw, DefaultPathItemRaw, HttpMethod},
+        schema::Apiv2Operation,
+    };
+
+    #[derive(Serialize, Apiv2Schema)]
+    #[openapi(example = r#"{"name": "Rex"}"#, example_ref = "SharedPet")]
+    struct SharedPet {
+        name: String,
+    }
+
+    #[api_v2_operation]
+    async fn get_pet() -> web::Json<SharedPet> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation]
+    async fn latest_pet() -> web::Json<SharedPet> {
+        unimplemented!()
+    }
+
+    let mut api = DefaultApiRaw {
+        definitions: paperclip_get_pet::definitions(),
+        ..Default::default()
+    };
+    for (path, op) in [
+        ("/pet", paperclip_get_pet::operation()),
+        ("/pets/latest", paperclip_latest_pet::operation()),
+    ] {
+        let mut item = DefaultPathItemRaw::default();
+        item.methods.insert(HttpMethod::Get, op);
+        api.paths.insert(path.into(), item);
+    }
+
+    let spec = serde_json::to_value(openapiv3::OpenAPI::from(api)).unwrap();
+    assert_eq!(
+        spec["components"]["examples"],
+        json!({ "SharedPet": { "value": { "name": "Rex" } } })
+    );
+    assert!(spec["components"]["schemas"]["SharedPet"]
+        .get("example")
+        .is_none());
+    for path in ["/pet", "/pets/latest"] {
+        assert_eq!(
+            spec["paths"][path]["get"]["responses"]["200"]["content"]["application/json"]
+                ["examples"],
+            json!({ "SharedPet": { "$ref": "#/components/examples/SharedPet" } })
+        );
+    }
+}
+
+#[test]
+#[cfg(all(feature = "actix4", feature = "actix-multipart"))]
+fn test_multipart_form() {
+    use actix_multipart::form::{tempfile::TempFile, text::Text, MultipartForm};
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(MultipartForm, Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Upload {
+        /// Name of the uploaded file
+        name: Text<String>,
+        file: TempFile,
+    }
+
+    #[api_v2_operation]
+    async fn upload(_form: MultipartForm<Upload>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let op = paperclip_upload::operation();
+    assert_eq!(
+        serde_json::to_value(&op).unwrap(),
+        json!({
+            "consumes": ["multipart/form-data"],
+            "parameters": [
+                {
+                    "format": "binary",
+                    "in": "formData",
+                    "name": "file",
+                    "required": true,
+                    "type": "file"
+                },
+                {
+                    "description": "Name of the uploaded file",
+                    "in": "formData",
+                    "name": "name",
+                    "required": true,
+                    "type": "string"
+                }
+            ],
+            "responses": {
+                "204": {
+                    "description": "No Content"
+                }
+            }
+        })
+    );
+    assert!(paperclip_upload::definitions().is_empty());
+}
+
+#[test]
+fn test_xml_metadata() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[openapi(xml(name = "pet", namespace = "https://example.com/schema", prefix = "ex"))]
+    struct Pet {
+        #[openapi(xml(attribute))]
+        id: u64,
+        #[openapi(xml(name = "tag", wrapped))]
+        tags: Vec<String>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Pet::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "id": {
+                    "format": "int64",
+                    "type": "integer",
+                    "xml": {
+                        "attribute": true
+                    }
+                },
+                "tags": {
+                    "items": {
+                        "type": "string"
+                    },
+                    "type": "array",
+                    "xml": {
+                        "name": "tag",
+                        "wrapped": true
+                    }
+                }
+            },
+            "required": ["id", "tags"],
+            "type": "object",
+            "xml": {
+                "name": "pet",
+                "namespace": "https://example.com/schema",
+                "prefix": "ex"
+            }
+        })
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let schema: openapiv3::ReferenceOr<openapiv3::Schema> = Pet::raw_schema().into();
+        let schema = serde_json::to_value(schema).unwrap();
+        assert_eq!(schema["xml"]["name"], "pet");
+        assert_eq!(
+            schema["properties"]["id"]["xml"],
+            json!({ "attribute": true })
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_operation_described_responses() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[api_v2_operation(
+        response(302, description = "Redirect to the pet's new home"),
+        response(304)
+    )]
+    async fn find_pet() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    assert_eq!(
+        serde_json::to_value(paperclip_find_pet::operation().responses).unwrap(),
+        json!({
+            "204": {
+                "description": "No Content"
+            },
+            "302": {
+                "description": "Redirect to the pet's new home"
+            },
+            "304": {
+                "description": "Not Modified"
+            }
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_operation_described_typed_response() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct JobTicket {
+        ticket: String,
+    }
+
+    #[api_v2_operation(
+        responses(202 = "JobTicket"),
+        response(202, description = "Queued for processing")
+    )]
+    async fn run_job() -> actix_web::HttpResponse {
+        unimplemented!()
+    }
+
+    assert_eq!(
+        serde_json::to_value(&paperclip_run_job::operation().responses["202"]).unwrap(),
+        json!({
+            "description": "Queued for processing",
+            "schema": {
+                "$ref": "#/definitions/JobTicket"
+            }
+        })
+    );
+}
+
+#[test]
+fn test_field_deprecated_note() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[allow(deprecated)]
+    struct Owner {
+        /// Full name of the owner
+        #[openapi(deprecated = "use `first_name` and `last_name` instead")]
+        name: String,
+        #[openapi(deprecated)]
+        nickname: Option<String>,
+        #[deprecated]
+        age: Option<u8>,
+        first_name: String,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Owner::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "age": {
+                    "deprecated": true,
+                    "format": "int32",
+                    "type": "integer"
+                },
+                "first_name": {
+                    "type": "string"
+                },
+                "name": {
+                    "deprecated": true,
+                    "description": "Full name of the owner\n\nDeprecated: use `first_name` and `last_name` instead",
+                    "type": "string"
+                },
+                "nickname": {
+                    "deprecated": true,
+                    "type": "string"
+                }
+            },
+            "required": ["first_name", "name"],
+            "type": "object"
+        })
+    );
+}
+
+#[test]
+fn test_all_definitions() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    enum Species {
+        Dog,
+        Cat,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Owner {
+        name: String,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Pet {
+        species: Species,
+        owners: Vec<Owner>,
+        friends: std::collections::BTreeMap<String, Pet>,
+    }
+
+    let definitions = Pet::all_definitions();
+    assert_eq!(
+        definitions.keys().collect::<Vec<_>>(),
+        vec!["Owner", "Pet", "Species"]
+    );
+    assert_eq!(
+        serde_json::to_value(&definitions["Pet"].properties["friends"]).unwrap(),
+        json!({
+            "additionalProperties": {
+                "$ref": "#/definitions/Pet"
+            },
+            "type": "object"
+        })
+    );
+    assert!(String::all_definitions().is_empty());
+}
+
+#[test]
+#[cfg(all(feature = "actix4", feature = "v3"))]
+fn test_request_body_media_types_v3() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(Deserialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    struct NewPet {
+        name: String,
+    }
+
+    #[api_v2_operation(consumes = "application/json, application/msgpack")]
+    async fn add_pet(_body: web::Json<NewPet>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let op = openapiv3::Operation::from(paperclip_add_pet::operation());
+    let body = serde_json::to_value(op.request_body).unwrap();
+    let schema = json!({ "schema": { "$ref": "#/components/schemas/NewPet" } });
+    assert_eq!(
+        body["content"],
+        json!({
+            "application/json": schema,
+            "application/msgpack": schema
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_binary_request_body() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[api_v2_operation]
+    async fn upload_photo(_body: web::Bytes) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation(consumes = "image/png")]
+    async fn upload_png(_body: web::Payload) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let op = serde_json::to_value(paperclip_upload_photo::operation()).unwrap();
+    assert_eq!(op["consumes"], json!(["application/octet-stream"]));
+    assert_eq!(
+        op["parameters"],
+        json!([{
+            "in": "body",
+            "name": "body",
+            "required": true,
+            "schema": {
+                "format": "binary",
+                "type": "string"
+            }
+        }])
+    );
+    assert!(paperclip_upload_photo::definitions().is_empty());
+
+    let op = serde_json::to_value(paperclip_upload_png::operation()).unwrap();
+    assert_eq!(op["consumes"], json!(["image/png"]));
+    assert_eq!(op["parameters"][0]["schema"]["format"], "binary");
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_tail_path_parameter() {
+    use paperclip::v2::schema::Apiv2Operation;
+    use std::path::PathBuf;
+
+    #[api_v2_operation]
+    async fn get_file(_tail: web::Path<(u32, PathBuf)>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let mut op = paperclip_get_file::operation();
+    op.set_parameter_names_from_path_template("/repos/{id:\\d+}/files/{tail:.*}");
+    assert_eq!(
+        serde_json::to_value(&op.parameters).unwrap(),
+        json!([
+            {
+                "format": "int32",
+                "in": "path",
+                "name": "id",
+                "pattern": "\\d+",
+                "required": true,
+                "type": "integer"
+            },
+            {
+                "in": "path",
+                "name": "tail",
+                "required": true,
+                "type": "string"
+            }
+        ])
+    );
+}
+
+#[test]
+fn test_openapi_rename_all() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Apiv2Schema)]
+    #[openapi(rename_all = "SCREAMING_SNAKE_CASE")]
+    #[allow(dead_code)]
+    enum Status {
+        Available,
+        OnHold,
+    }
+
+    #[derive(Apiv2Schema)]
+    #[openapi(rename_all = "camelCase")]
+    #[allow(dead_code)]
+    struct Pet {
+        pet_name: String,
+        date_of_birth: Option<String>,
+        status: Status,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Pet::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "dateOfBirth": {
+                    "type": "string"
+                },
+                "petName": {
+                    "type": "string"
+                },
+                "status": {
+                    "enum": ["AVAILABLE", "ON_HOLD"],
+                    "type": "string"
+                }
+            },
+            "required": ["petName", "status"],
+            "type": "object"
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_operation_response_examples() {
+    use paperclip::v2::{models::Either, schema::Apiv2Operation};
+
+    #[api_v2_operation(examples(
+        cat = r#"{"name": "Tom", "class": "cat"}"#,
+        dog = r#"{"name": "Rex", "class": "dog"}"#
+    ))]
+    async fn get_pet() -> web::Json<Pet> {
+        unimplemented!()
+    }
+
+    let op = paperclip_get_pet::operation();
+    let response = match &op.responses["200"] {
+        Either::Right(response) => response,
+        Either::Left(_) => panic!("expected inline response"),
+    };
+    assert_eq!(response.examples["cat"]["name"], "Tom");
+    assert_eq!(response.examples["dog"]["class"], "dog");
+    // Not part of the v2 response object.
+    assert!(serde_json::to_value(response)
+        .unwrap()
+        .get("examples")
+        .is_none());
+
+    #[cfg(feature = "v3")]
+    {
+        let op = serde_json::to_value(openapiv3::Operation::from(op)).unwrap();
+        assert_eq!(
+            op["responses"]["200"]["content"]["application/json"]["examples"],
+            json!({
+                "cat": { "value": { "name": "Tom", "class": "cat" } },
+                "dog": { "value": { "name": "Rex", "class": "dog" } }
+            })
+        );
+    }
+}
+
+#[test]
+fn test_ip_formats() {
+    use paperclip::v2::schema::Apiv2Schema;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Host {
+        ip: IpAddr,
+        ip_v4: Ipv4Addr,
+        ip_v6: Option<Ipv6Addr>,
+    }
+
+    let expected = json!({
+        "ip": {
+            "format": "ip",
+            "type": "string"
+        },
+        "ip_v4": {
+            "format": "ipv4",
+            "type": "string"
+        },
+        "ip_v6": {
+            "format": "ipv6",
+            "type": "string"
+        }
+    });
+    assert_eq!(
+        serde_json::to_value(Host::raw_schema()).unwrap()["properties"],
+        expected
+    );
+
+    // v3 doesn't define these formats, so they're carried over as-is.
+    #[cfg(feature = "v3")]
+    {
+        let schema: openapiv3::ReferenceOr<openapiv3::Schema> = Host::raw_schema().into();
+        assert_eq!(
+            serde_json::to_value(schema).unwrap()["properties"],
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_time_formats() {
+    use paperclip::v2::schema::Apiv2Schema;
+    use std::time::{Duration, SystemTime};
+
+    #[derive(Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Lease {
+        duration: Duration,
+        expires_at: SystemTime,
+        renewed_at: Option<SystemTime>,
+    }
+
+    let schema = Lease::raw_schema();
+    assert_eq!(
+        serde_json::to_value(&schema).unwrap()["properties"],
+        json!({
+            "duration": {
+                "format": "int64",
+                "type": "integer"
+            },
+            "expires_at": {
+                "format": "date-time",
+                "type": "string"
+            },
+            "renewed_at": {
+                "format": "date-time",
+                "type": "string"
+            }
+        })
+    );
+    assert_eq!(
+        schema.required,
+        ["duration", "expires_at"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_non_zero_integers() {
+    use paperclip::v2::schema::Apiv2Schema;
+    use std::num::{NonZeroI64, NonZeroU32, NonZeroU64};
+
+    #[derive(Deserialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Page {
+        size: NonZeroU32,
+        cursor: Option<NonZeroU64>,
+        offset: NonZeroI64,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Page::raw_schema()).unwrap()["properties"],
+        json!({
+            "cursor": {
+                "format": "int64",
+                "minimum": 1.0,
+                "type": "integer"
+            },
+            "offset": {
+                "format": "int64",
+                "type": "integer"
+            },
+            "size": {
+                "format": "int32",
+                "minimum": 1.0,
+                "type": "integer"
+            }
+        })
+    );
+
+    #[api_v2_operation]
+    async fn get_page(_id: web::Path<NonZeroU64>, _page: web::Query<Page>) -> NoContent {
+        NoContent
+    }
+
+    let params = paperclip_get_page::raw_operation().parameters;
+    let minimum = |name: &str| {
+        params
+            .iter()
+            .filter_map(|p| p.right())
+            .find(|p| p.name == name)
+            .unwrap()
+            .minimum
+    };
+    assert_eq!(minimum(""), Some(1.0));
+    assert_eq!(minimum("size"), Some(1.0));
+    assert_eq!(minimum("offset"), None);
+
+    // The bound is kept in v3.
+    #[cfg(feature = "v3")]
+    {
+        let schema: openapiv3::ReferenceOr<openapiv3::Schema> = Page::raw_schema().into();
+        let schema = serde_json::to_value(schema).unwrap();
+        assert_eq!(
+            schema["properties"]["size"],
+            json!({
+                "format": "int32",
+                "minimum": 1,
+                "type": "integer"
+            })
+        );
+        assert!(schema["properties"]["offset"].get("minimum").is_none());
+    }
+}
+
+#[test]
+#[cfg(all(feature = "chrono", feature = "url"))]
+fn test_url_and_chrono_formats() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Webhook {
+        url: url_dep::Url,
+        created_at: chrono_dev::DateTime<chrono_dev::Utc>,
+        first_delivery: Option<chrono_dev::NaiveDate>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Webhook::raw_schema()).unwrap()["properties"],
+        json!({
+            "created_at": {
+                "format": "date-time",
+                "type": "string"
+            },
+            "first_delivery": {
+                "format": "date",
+                "type": "string"
+            },
+            "url": {
+                "format": "url",
+                "type": "string"
+            }
+        })
+    );
+}
+
+#[test]
+fn test_set_unique_items() {
+    use paperclip::v2::schema::Apiv2Schema;
+    use std::collections::BTreeSet;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Pet {
+        tags: HashSet<String>,
+        owner_ids: BTreeSet<u64>,
+        photos: Vec<String>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Pet::raw_schema()).unwrap()["properties"],
+        json!({
+            "owner_ids": {
+                "items": {
+                    "format": "int64",
+                    "type": "integer"
+                },
+                "type": "array",
+                "uniqueItems": true
+            },
+            "photos": {
+                "items": {
+                    "type": "string"
+                },
+                "type": "array"
+            },
+            "tags": {
+                "items": {
+                    "type": "string"
+                },
+                "type": "array",
+                "uniqueItems": true
+            }
+        })
+    );
+}
+
+#[test]
+fn test_tuple_field() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Place {
+        label: (i32, String),
+        coordinates: (f64, f64),
+    }
+
+    assert_eq!(
+        serde_json::to_value(Place::raw_schema()).unwrap(),
+        json!({
+            "properties": {
+                "coordinates": {
+                    "items": {
+                        "format": "double",
+                        "type": "number"
+                    },
+                    "maxItems": 2,
+                    "minItems": 2,
+                    "type": "array"
+                },
+                "label": {
+                    "items": {
+                        "anyOf": [
+                            {
+                                "format": "int32",
+                                "type": "integer"
+                            },
+                            {
+                                "type": "string"
+                            }
+                        ]
+                    },
+                    "maxItems": 2,
+                    "minItems": 2,
+                    "type": "array"
+                }
+            },
+            "required": ["coordinates", "label"],
+            "type": "object"
+        })
+    );
+}
+
+#[test]
+fn test_smart_pointer_fields() {
+    use paperclip::v2::schema::Apiv2Schema;
+    use std::{rc::Rc, sync::Arc};
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Owner {
+        name: String,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Boxed {
+        owner: Box<Owner>,
+        previous: Option<Box<Owner>>,
+        name: Rc<String>,
+        tags: Arc<Vec<String>>,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Plain {
+        owner: Owner,
+        previous: Option<Owner>,
+        name: String,
+        tags: Vec<String>,
+    }
+
+    let mut boxed = serde_json::to_value(Boxed::raw_schema()).unwrap();
+    let mut plain = serde_json::to_value(Plain::raw_schema()).unwrap();
+    assert_eq!(boxed["properties"]["owner"], plain["properties"]["owner"]);
+    assert_eq!(
+        boxed["properties"]["previous"],
+        plain["properties"]["previous"]
+    );
+    assert_eq!(boxed["properties"]["name"], json!({ "type": "string" }));
+    assert_eq!(
+        boxed["properties"]["tags"],
+        json!({ "items": { "type": "string" }, "type": "array" })
+    );
+    boxed["properties"].take();
+    plain["properties"].take();
+    assert_eq!(boxed, plain);
+}
+
+#[test]
+fn test_free_form_value_field() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Event {
+        name: String,
+        payload: serde_json::Value,
+        metadata: Option<serde_json::Value>,
+    }
+
+    let schema = serde_json::to_value(Event::raw_schema()).unwrap();
+    assert_eq!(schema["properties"]["payload"], json!({}));
+    assert_eq!(schema["properties"]["metadata"], json!({}));
+    assert_eq!(schema["required"], json!(["name", "payload"]));
+
+    #[cfg(feature = "v3")]
+    {
+        let schema: openapiv3::ReferenceOr<openapiv3::Schema> = Event::raw_schema().into();
+        let schema = serde_json::to_value(schema).unwrap();
+        assert_eq!(schema["properties"]["payload"], json!({}));
+    }
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_generic_page_definitions() {
+    use paperclip::v2::{models::DefaultSchemaRaw, schema::Apiv2Operation};
+
+    #[derive(Serialize, Apiv2Schema)]
+    struct User {
+        name: String,
+    }
+
+    #[derive(Serialize, Apiv2Schema)]
+    struct Order {
+        total: u32,
+    }
+
+    #[derive(Serialize, Apiv2Schema)]
+    struct Page<T> {
+        items: Vec<T>,
+        next_cursor: Option<String>,
+    }
+
+    #[api_v2_operation]
+    async fn list_users() -> web::Json<Page<User>> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation]
+    async fn list_orders() -> web::Json<Page<Order>> {
+        unimplemented!()
+    }
+
+    let mut definitions = paperclip_list_users::definitions();
+    definitions.extend(paperclip_list_orders::definitions());
+    assert_eq!(
+        definitions.keys().collect::<Vec<_>>(),
+        vec!["Page<Order>", "Page<User>"]
+    );
+    let items = |def: &DefaultSchemaRaw| serde_json::to_value(&def.properties["items"]).unwrap();
+    assert_eq!(
+        items(&definitions["Page<User>"])["items"]["properties"]["name"]["type"],
+        "string"
+    );
+    assert_eq!(
+        items(&definitions["Page<Order>"])["items"]["properties"]["total"]["type"],
+        "integer"
+    );
+
+    for (op, name) in [
+        (paperclip_list_users::operation(), "User"),
+        (paperclip_list_orders::operation(), "Order"),
+    ] {
+        assert_eq!(
+            serde_json::to_value(&op.responses["200"]).unwrap()["schema"],
+            json!({ "$ref": format!("#/definitions/Page%3C{}%3E", name) })
+        );
+    }
+}
+
+#[test]
+fn test_unit_struct_const_value() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    /// Marks the kind of a dog.
+    #[derive(Apiv2Schema)]
+    #[openapi(const_value = "dog")]
+    struct DogKind;
+
+    #[derive(Apiv2Schema)]
+    #[openapi(const_value = "2")]
+    struct Version2;
+
+    assert_eq!(
+        serde_json::to_value(DogKind::raw_schema()).unwrap(),
+        json!({
+            "enum": ["dog"],
+            "type": "string"
+        })
+    );
+    assert_eq!(DogKind::description(), "Marks the kind of a dog.");
+    assert_eq!(
+        serde_json::to_value(Version2::raw_schema()).unwrap(),
+        json!({
+            "enum": [2],
+            "type": "integer"
+        })
+    );
+}
+
+#[test]
+#[deny(warnings)]
+fn test_empty_schema_with_reason() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Apiv2Schema)]
+    #[openapi(empty = "marker type, only used for routing")]
+    struct Marker;
+
+    #[derive(Apiv2Schema)]
+    #[openapi(empty)]
+    struct OtherMarker;
+
+    assert_eq!(Marker::name(), None);
+    assert_eq!(
+        serde_json::to_value(Marker::raw_schema()).unwrap(),
+        json!({})
+    );
+    assert_eq!(OtherMarker::name(), None);
+    assert_eq!(
+        serde_json::to_value(OtherMarker::raw_schema()).unwrap(),
+        json!({})
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_req_data_extractor() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(Clone)]
+    struct CurrentUser {
+        _id: u64,
+    }
+
+    #[derive(Deserialize, Apiv2Schema)]
+    struct Filter {
+        _q: String,
+    }
+
+    #[api_v2_operation]
+    async fn list_items(
+        _user: web::ReqData<CurrentUser>,
+        _filter: web::Query<Filter>,
+    ) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let op = serde_json::to_value(paperclip_list_items::operation()).unwrap();
+    assert_eq!(
+        op["parameters"],
+        json!([{
+            "in": "query",
+            "name": "_q",
+            "required": true,
+            "type": "string"
+        }])
+    );
+    assert!(paperclip_list_items::definitions().is_empty());
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_query_parameter_example() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(Deserialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Search {
+        #[openapi(example = "dog")]
+        q: String,
+        #[openapi(example = "20")]
+        limit: Option<u32>,
+    }
+
+    #[api_v2_operation]
+    async fn search(_query: web::Query<Search>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let op = serde_json::to_value(paperclip_search::operation()).unwrap();
+    assert_eq!(
+        op["parameters"],
+        json!([
+            {
+                "format": "int32",
+                "in": "query",
+                "name": "limit",
+                "type": "integer",
+                "x-example": 20
+            },
+            {
+                "in": "query",
+                "name": "q",
+                "required": true,
+                "type": "string",
+                "x-example": "dog"
+            }
+        ])
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let op = openapiv3::Operation::from(paperclip_search::operation());
+        let params = serde_json::to_value(op.parameters).unwrap();
+        assert_eq!(params[0]["example"], json!(20));
+        assert_eq!(params[1]["example"], json!("dog"));
+    }
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_deprecated_query_parameter() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(Deserialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Search {
+        q: String,
+        /// Use `q` instead.
+        #[openapi(deprecated)]
+        query: Option<String>,
+    }
+
+    #[api_v2_operation]
+    async fn search(_query: web::Query<Search>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let op = serde_json::to_value(paperclip_search::operation()).unwrap();
+    assert_eq!(op["deprecated"], json!(null));
+    assert_eq!(
+        op["parameters"],
+        json!([
+            {
+                "in": "query",
+                "name": "q",
+                "required": true,
+                "type": "string"
+            },
+            {
+                "description": "Use `q` instead.",
+                "in": "query",
+                "name": "query",
+                "type": "string",
+                "x-deprecated": true
+            }
+        ])
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let op = openapiv3::Operation::from(paperclip_search::operation());
+        let params = serde_json::to_value(op.parameters).unwrap();
+        assert_eq!(params[0]["deprecated"], json!(null));
+        assert_eq!(params[1]["deprecated"], json!(true));
+    }
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_query_parameter_allow_empty_value() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(Deserialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Filter {
+        /// Only list archived items (`?archived`).
+        #[openapi(allow_empty_value)]
+        archived: Option<String>,
+    }
+
+    #[api_v2_operation]
+    async fn list_items(_query: web::Query<Filter>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let op = serde_json::to_value(paperclip_list_items::operation()).unwrap();
+    assert_eq!(
+        op["parameters"],
+        json!([{
+            "allowEmptyValue": true,
+            "description": "Only list archived items (`?archived`).",
+            "in": "query",
+            "name": "archived",
+            "type": "string"
+        }])
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let op = openapiv3::Operation::from(paperclip_list_items::operation());
+        let params = serde_json::to_value(op.parameters).unwrap();
+        assert_eq!(params[0]["allowEmptyValue"], json!(true));
+    }
+
+    #[api_v2_operation]
+    async fn archive_items(_path: web::Path<Filter>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let op = serde_json::to_value(paperclip_archive_items::operation()).unwrap();
+    assert!(op["parameters"][0].get("allowEmptyValue").is_none());
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_get_request_body() {
+    #[derive(Deserialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Criteria {
+        name: String,
+    }
+
+    #[api_v2_operation]
+    async fn search(_body: web::Json<Criteria>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation(allow_get_body)]
+    async fn legacy_search(_body: web::Json<Criteria>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    run_and_check_app(
+        || {
+            App::new()
+                .wrap_api()
+                .with_json_spec_at("/api/spec")
+                .service(
+                    web::resource("/search")
+                        .route(web::get().to(search))
+                        .route(web::post().to(search)),
+                )
+                .route("/legacy/search", web::get().to(legacy_search))
+                .build()
+        },
+        |addr| {
+            let resp = CLIENT
+                .get(&format!("http://{}/api/spec", addr))
+                .send()
+                .expect("request failed?");
+            let json = resp.json::<serde_json::Value>().expect("json error");
+            let body = json!([{
+                "in": "body",
+                "name": "body",
+                "required": true,
+                "schema": {
+                    "$ref": "#/definitions/Criteria"
+                }
+            }]);
+
+            assert!(json["paths"]["/search"]["get"].get("parameters").is_none());
+            assert_eq!(json["paths"]["/search"]["post"]["parameters"], body);
+            assert_eq!(json["paths"]["/legacy/search"]["get"]["parameters"], body);
+        },
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_form_request_body() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(Deserialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Login {
+        username: String,
+        password: String,
+        remember: Option<bool>,
+    }
+
+    #[api_v2_operation]
+    async fn login(_form: web::Form<Login>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let op = serde_json::to_value(paperclip_login::operation()).unwrap();
+    assert_eq!(op["consumes"], json!(["application/x-www-form-urlencoded"]));
+    assert_eq!(
+        op["parameters"],
+        json!([
+            {
+                "in": "formData",
+                "name": "password",
+                "required": true,
+                "type": "string"
+            },
+            {
+                "in": "formData",
+                "name": "remember",
+                "type": "boolean"
+            },
+            {
+                "in": "formData",
+                "name": "username",
+                "required": true,
+                "type": "string"
+            }
+        ])
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let op = openapiv3::Operation::from(paperclip_login::operation());
+        let body = serde_json::to_value(op.request_body).unwrap();
+        let schema = &body["content"]["application/x-www-form-urlencoded"]["schema"];
+        assert_eq!(schema["required"], json!(["password", "username"]));
+        assert_eq!(schema["properties"]["remember"]["type"], "boolean");
+    }
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_read_only_required_request_body() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Pet {
+        /// Assigned by the server.
+        #[serde(skip_deserializing)]
+        id: u64,
+        name: String,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Owner {
+        name: String,
+    }
+
+    #[api_v2_operation]
+    async fn add_pet(body: web::Json<Pet>) -> Result<web::Json<Pet>, Error> {
+        Ok(body)
+    }
+
+    #[api_v2_operation]
+    async fn add_owner(body: web::Json<Owner>) -> Result<web::Json<Owner>, Error> {
+        Ok(body)
+    }
+
+    let op = serde_json::to_value(paperclip_add_pet::operation()).unwrap();
+    assert_eq!(
+        op["parameters"][0]["schema"],
+        json!({
+            "properties": {
+                "id": {
+                    "description": "Assigned by the server.",
+                    "format": "int64",
+                    "readOnly": true,
+                    "type": "integer"
+                },
+                "name": {
+                    "type": "string"
+                }
+            },
+            "required": ["name"],
+            "type": "object"
+        })
+    );
+    assert_eq!(
+        op["responses"]["200"]["schema"],
+        json!({ "$ref": "#/definitions/Pet" })
+    );
+    assert_eq!(
+        serde_json::to_value(&paperclip_add_pet::definitions()["Pet"]).unwrap()["required"],
+        json!(["id", "name"])
+    );
+
+    // Schemas without required read-only fields are still referenced.
+    let op = serde_json::to_value(paperclip_add_owner::operation()).unwrap();
+    assert_eq!(
+        op["parameters"][0]["schema"],
+        json!({ "$ref": "#/definitions/Owner" })
+    );
+}
+
+#[test]
+fn test_newtype_format() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    /// Email address of a user.
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[openapi(format = "email")]
+    struct Email(String);
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Website(#[openapi(format = "url")] String);
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Signup {
+        email: Email,
+        website: Option<Website>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Signup::raw_schema()).unwrap()["properties"],
+        json!({
+            "email": {
+                "description": "Email address of a user.",
+                "format": "email",
+                "type": "string"
+            },
+            "website": {
+                "format": "url",
+                "type": "string"
+            }
+        })
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let schema = openapiv3::ReferenceOr::<openapiv3::Schema>::from(Signup::raw_schema());
+        let json = serde_json::to_value(schema).unwrap();
+        assert_eq!(json["properties"]["email"]["format"], "email");
+        assert_eq!(json["properties"]["website"]["format"], "url");
+    }
+}
+
+#[test]
+#[cfg(feature = "v3")]
+fn test_prune_unused_definitions() {
+    let mut spec: openapiv3::OpenAPI = serde_json::from_value(json!({
+        "openapi": "3.0.0",
+        "info": { "title": "", "version": "" },
+        "paths": {
+            "/pets": {
+                "get": {
+                    "responses": {
+                        "200": {
+                            "description": "OK",
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/Page%3CPet%3E" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "Page<Pet>": {
+                    "type": "object",
+                    "properties": {
+                        "items": {
+                            "type": "array",
+                            "items": { "$ref": "#/components/schemas/Pet" }
+                        }
+                    }
+                },
+                "Pet": {
+                    "type": "object",
+                    "properties": {
+                        "owner": { "$ref": "#/components/schemas/Owner" }
+                    }
+                },
+                "Owner": { "type": "object" },
+                "Order": {
+                    "type": "object",
+                    "properties": {
+                        "buyer": { "$ref": "#/components/schemas/Buyer" }
+                    }
+                },
+                "Buyer": { "type": "object" }
+            }
+        }
+    }))
+    .unwrap();
+
+    paperclip::v3::prune_unused_definitions(&mut spec);
+    let mut names = spec
+        .components
+        .unwrap()
+        .schemas
+        .into_keys()
+        .collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(names, ["Owner", "Page<Pet>", "Pet"]);
+}
+
+#[test]
+#[cfg(feature = "v3")]
+fn test_canonicalize_v3_spec() {
+    fn assemble(names: &[&str]) -> openapiv3::OpenAPI {
+        let mut spec: openapiv3::OpenAPI = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": { "title": "", "version": "" },
+            "paths": {}
+        }))
+        .unwrap();
+        let mut components = openapiv3::Components::default();
+        let mut root: openapiv3::Schema =
+            serde_json::from_value(json!({ "type": "object" })).unwrap();
+        for name in names {
+            if let openapiv3::SchemaKind::Type(openapiv3::Type::Object(object)) =
+                &mut root.schema_kind
+            {
+                object.properties.insert(
+                    name.to_lowercase(),
+                    openapiv3::ReferenceOr::ref_(&format!("#/components/schemas/{}", name)),
+                );
+            }
+            components.schemas.insert(
+                name.to_string(),
+                openapiv3::ReferenceOr::Item(
+                    serde_json::from_value(json!({ "type": "string" })).unwrap(),
+                ),
+            );
+            spec.paths.paths.insert(
+                format!("/{}", name.to_lowercase()),
+                openapiv3::ReferenceOr::Item(Default::default()),
+            );
+        }
+        components
+            .schemas
+            .insert("Root".into(), openapiv3::ReferenceOr::Item(root));
+        spec.components = Some(components);
+        spec
+    }
+
+    let mut first = assemble(&["Pet", "Owner", "Address"]);
+    let mut second = assemble(&["Address", "Pet", "Owner"]);
+    assert_ne!(
+        serde_json::to_string(&first).unwrap(),
+        serde_json::to_string(&second).unwrap()
+    );
+
+    paperclip::v3::canonicalize(&mut first);
+    paperclip::v3::canonicalize(&mut second);
+    let json = serde_json::to_string(&first).unwrap();
+    assert_eq!(json, serde_json::to_string(&second).unwrap());
+    assert!(json.find("\"/address\"").unwrap() < json.find("\"/owner\"").unwrap());
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_duplicate_operation_ids() {
+    use paperclip::v2::models::{DefaultPathItemRaw, HttpMethod};
+    use paperclip::v2::schema::Apiv2Operation;
+    use paperclip_core::ValidationError;
+
+    #[api_v2_operation(operation_id = "listPets")]
+    async fn list_pets() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation(operation_id = "listPets")]
+    async fn list_archived_pets() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation(operation_id = "addPet")]
+    async fn add_pet() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let mut spec = DefaultApiRaw::default();
+    let mut item = DefaultPathItemRaw::default();
+    item.methods
+        .insert(HttpMethod::Get, paperclip_list_pets::operation());
+    item.methods
+        .insert(HttpMethod::Post, paperclip_add_pet::operation());
+    spec.paths.insert("/pets".into(), item);
+    assert!(spec.check_operation_ids().is_ok());
+
+    let mut item = DefaultPathItemRaw::default();
+    item.methods
+        .insert(HttpMethod::Get, paperclip_list_archived_pets::operation());
+    spec.paths.insert("/pets/archived".into(), item);
+    match spec.check_operation_ids() {
+        Err(ValidationError::DuplicateOperationIds(ids)) => assert_eq!(
+            ids,
+            vec![(
+                "listPets".to_owned(),
+                vec!["GET /pets".to_owned(), "GET /pets/archived".to_owned()]
+            )]
+        ),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_scope_tags() {
+    #[api_v2_operation(tags(Pets))]
+    async fn list_pets() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation]
+    async fn add_pet() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation]
+    async fn list_owners() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    run_and_check_app(
+        || {
+            App::new()
+                .wrap_api()
+                .with_json_spec_at("/api/spec")
+                .service(
+                    web::scope("/v1")
+                        .tags(["Public"])
+                        .service(
+                            web::scope("/pets")
+                                .tags(["Pets"])
+                                .route("/", web::get().to(list_pets))
+                                .route("/", web::post().to(add_pet)),
+                        )
+                        .route("/owners", web::get().to(list_owners)),
+                )
+                .build()
+        },
+        |addr| {
+            let resp = CLIENT
+                .get(&format!("http://{}/api/spec", addr))
+                .send()
+                .expect("request failed?");
+            let paths = &resp.json::<serde_json::Value>().expect("json error")["paths"];
+
+            assert_eq!(paths["/v1/pets/"]["get"]["tags"], json!(["Pets", "Public"]));
+            assert_eq!(
+                paths["/v1/pets/"]["post"]["tags"],
+                json!(["Pets", "Public"])
+            );
+            assert_eq!(paths["/v1/owners"]["get"]["tags"], json!(["Public"]));
+        },
+    );
+}
+
+#[test]
+#[cfg(feature = "v3")]
+fn test_root_servers_v3() {
+    use paperclip::v2::models::{Server, ServerVariable};
+
+    let api = DefaultApiRaw {
+        host: Some("ignored.example.com".into()),
+        servers: vec![
+            Server::new("https://{region}.api.example.com/v1")
+                .description("Regional endpoint")
+                .variable(
+                    "region",
+                    ServerVariable::new("eu")
+                        .enum_values(["eu", "us"])
+                        .description("Deployment region"),
+                ),
+            Server::new("http://localhost:8080/v1"),
+        ],
+        ..Default::default()
+    };
+
+    let spec = serde_json::to_value(paperclip::v3::openapiv2_to_v3(api)).unwrap();
+    assert_eq!(
+        spec["servers"],
+        json!([
+            {
+                "url": "https://{region}.api.example.com/v1",
+                "description": "Regional endpoint",
+                "variables": {
+                    "region": {
+                        "enum": ["eu", "us"],
+                        "default": "eu",
+                        "description": "Deployment region"
+                    }
+                }
+            },
+            {
+                "url": "http://localhost:8080/v1"
+            }
+        ])
+    );
+}
+
+#[test]
+fn test_info_builder() {
+    use paperclip::v2::models::{Contact, License};
+
+    let api = DefaultApiRaw {
+        info: Info::new("Pet store", "1.2.0")
+            .description("Manages the pets in the store")
+            .terms_of_service("https://example.com/terms")
+            .contact(
+                Contact::new("API support")
+                    .url("https://example.com/support")
+                    .email("support@example.com"),
+            )
+            .license(License::new("MIT").url("https://opensource.org/licenses/MIT")),
+        ..Default::default()
+    };
+
+    let expected = json!({
+        "title": "Pet store",
+        "version": "1.2.0",
+        "description": "Manages the pets in the store",
+        "termsOfService": "https://example.com/terms",
+        "contact": {
+            "name": "API support",
+            "url": "https://example.com/support",
+            "email": "support@example.com"
+        },
+        "license": {
+            "name": "MIT",
+            "url": "https://opensource.org/licenses/MIT"
+        }
+    });
+    assert_eq!(serde_json::to_value(&api).unwrap()["info"], expected);
+
+    #[cfg(feature = "v3")]
+    {
+        let spec = serde_json::to_value(paperclip::v3::openapiv2_to_v3(api)).unwrap();
+        assert_eq!(spec["info"], expected);
+    }
+}
+
+#[test]
+#[cfg(all(feature = "actix4", feature = "oas31"))]
+fn test_webhooks() {
+    /// Notifies the subscriber about a new pet.
+    #[api_v2_operation]
+    async fn new_pet_hook(_body: web::Json<Pet>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation]
+    async fn list_pets() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    run_and_check_app(
+        || {
+            App::new()
+                .wrap_api()
+                .with_json_spec_v3_at("/api/spec/v3")
+                .route("/pets", web::get().to(list_pets))
+                .webhook("newPet", web::post().to(new_pet_hook))
+                .build()
+        },
+        |addr| {
+            let resp = CLIENT
+                .get(&format!("http://{}/api/spec/v3", addr))
+                .send()
+                .expect("request failed?");
+            let spec = resp.json::<serde_json::Value>().expect("json error");
+
+            // The schemas follow 3.0, so the webhooks are an extension of a 3.0 spec.
+            assert_eq!(spec["openapi"], json!("3.0.0"));
+            assert!(spec.get("webhooks").is_none());
+            assert!(spec["paths"]["/pets"]["get"].is_object());
+            assert!(spec["paths"].get("newPet").is_none());
+            let hook = &spec["x-webhooks"]["newPet"]["post"];
+            assert_eq!(
+                hook["summary"],
+                json!("Notifies the subscriber about a new pet.")
+            );
+            assert_eq!(
+                hook["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+                json!("#/components/schemas/Pet")
+            );
+            assert!(spec["components"]["schemas"]["Pet"].is_object());
+        },
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_operation_callbacks() {
+    #[derive(Serialize, Apiv2Schema)]
+    struct PetEvent {
+        pet_id: u64,
+    }
+
+    /// Notifies the subscriber about a new pet.
+    #[api_v2_operation]
+    #[allow(dead_code)]
+    async fn pet_added(_body: web::Json<PetEvent>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation(callback(
+        name = "onPetAdded",
+        path = "{$request.body#/callbackUrl}",
+        handler = "pet_added"
+    ))]
+    async fn subscribe() -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let op = paperclip_subscribe::raw_operation();
+    let callback = &op.callbacks["onPetAdded"]["{$request.body#/callbackUrl}"];
+    assert_eq!(
+        callback.methods[&paperclip::v2::models::HttpMethod::Post].summary,
+        Some("Notifies the subscriber about a new pet.".into())
+    );
+
+    run_and_check_app(
+        || {
+            let app = App::new()
+                .wrap_api()
+                .with_json_spec_at("/api/spec")
+                .route("/subscriptions", web::post().to(subscribe));
+            #[cfg(feature = "v3")]
+            let app = app.with_json_spec_v3_at("/api/spec/v3");
+            app.build()
+        },
+        |addr| {
+            let resp = CLIENT
+                .get(&format!("http://{}/api/spec", addr))
+                .send()
+                .expect("request failed?");
+            let spec = resp.json::<serde_json::Value>().expect("json error");
+            // Swagger 2.0 has no callbacks, but the definitions used by them are recorded.
+            assert!(spec["paths"]["/subscriptions"]["post"]
+                .get("callbacks")
+                .is_none());
+            assert!(spec["definitions"]["PetEvent"].is_object());
+
+            #[cfg(feature = "v3")]
+            {
+                let resp = CLIENT
+                    .get(&format!("http://{}/api/spec/v3", addr))
+                    .send()
+                    .expect("request failed?");
+                let spec = resp.json::<serde_json::Value>().expect("json error");
+                let callback = &spec["paths"]["/subscriptions"]["post"]["callbacks"]["onPetAdded"]
+                    ["{$request.body#/callbackUrl}"]["post"];
+                assert_eq!(
+                    callback["summary"],
+                    json!("Notifies the subscriber about a new pet.")
+                );
+                assert_eq!(
+                    callback["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+                    json!("#/components/schemas/PetEvent")
+                );
+            }
+        },
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_response_links() {
+    #[derive(Serialize, Apiv2Schema)]
+    struct CreatedPet {
+        id: u64,
+    }
+
+    #[api_v2_operation(
+        responses(201 = "CreatedPet"),
+        link(
+            201,
+            name = "GetPet",
+            operation_id = "getPet",
+            parameters(id = "$response.body#/id"),
+            description = "Fetches the created pet"
+        )
+    )]
+    async fn create_pet() -> actix_web::HttpResponse {
+        unimplemented!()
+    }
+
+    let op = paperclip_create_pet::raw_operation();
+    match &op.responses["201"] {
+        paperclip::v2::models::Either::Right(response) => {
+            let link = &response.links["GetPet"];
+            assert_eq!(link.operation_id, "getPet");
+            assert_eq!(link.parameters["id"], "$response.body#/id");
+            assert_eq!(link.description.as_deref(), Some("Fetches the created pet"));
+        }
+        _ => panic!("expected an inline response"),
+    }
+
+    #[cfg(feature = "v3")]
+    {
+        let mut api = DefaultApiRaw::default();
+        api.paths.entry("/pets".into()).or_default().methods.insert(
+            paperclip::v2::models::HttpMethod::Post,
+            paperclip_create_pet::raw_operation(),
+        );
+        let spec = serde_json::to_value(paperclip::v3::openapiv2_to_v3(api)).unwrap();
+        assert_eq!(
+            spec["paths"]["/pets"]["post"]["responses"]["201"]["links"],
+            json!({
+                "GetPet": {
+                    "operationId": "getPet",
+                    "parameters": { "id": "$response.body#/id" },
+                    "description": "Fetches the created pet"
+                }
+            })
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_success_description() {
+    #[api_v2_operation(success_description = "The pet with the given ID")]
+    async fn get_pet() -> Result<web::Json<Pet>, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation(success_description = "The pets")]
+    async fn list_pets() -> Result<web::Json<Vec<Pet>>, Error> {
+        unimplemented!()
+    }
+
+    run_and_check_app(
+        || {
+            App::new()
+                .wrap_api()
+                .with_json_spec_at("/api/spec")
+                .route("/pets", web::get().to(list_pets))
+                .route("/pets/{id}", web::get().to(get_pet))
+                .build()
+        },
+        |addr| {
+            let resp = CLIENT
+                .get(&format!("http://{}/api/spec", addr))
+                .send()
+                .expect("request failed?");
+            let paths = &resp.json::<serde_json::Value>().expect("json error")["paths"];
+
+            let response = &paths["/pets/{id}"]["get"]["responses"]["200"];
+            assert_eq!(response["description"], json!("The pet with the given ID"));
+            assert_eq!(response["schema"]["$ref"], json!("#/definitions/Pet"));
+            assert_eq!(
+                paths["/pets"]["get"]["responses"]["200"]["description"],
+                json!("The pets")
+            );
+        },
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_content_negotiation() {
+    #[derive(Serialize, Apiv2Schema)]
+    struct PetXml {
+        name: String,
+    }
+
+    #[derive(Serialize, Apiv2Schema)]
+    struct PetReport {
+        rows: Vec<String>,
+    }
+
+    #[api_v2_operation(
+        produces(json = "Pet", xml = "PetXml"),
+        produces("text/csv" = "PetReport")
+    )]
+    async fn get_pet() -> actix_web::HttpResponse {
+        unimplemented!()
+    }
+
+    let op = paperclip_get_pet::raw_operation();
+    let produces = op
+        .produces
+        .iter()
+        .flatten()
+        .map(|m| m.0.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        produces,
+        vec!["application/json", "application/xml", "text/csv"]
+    );
+
+    run_and_check_app(
+        || {
+            let app = App::new()
+                .wrap_api()
+                .with_json_spec_at("/api/spec")
+                .route("/pets/{id}", web::get().to(get_pet));
+            #[cfg(feature = "v3")]
+            let app = app.with_json_spec_v3_at("/api/spec/v3");
+            app.build()
+        },
+        |addr| {
+            let resp = CLIENT
+                .get(&format!("http://{}/api/spec", addr))
+                .send()
+                .expect("request failed?");
+            let spec = resp.json::<serde_json::Value>().expect("json error");
+            // Swagger 2.0 only has a single schema, which is the first one.
+            assert_eq!(
+                spec["paths"]["/pets/{id}"]["get"]["responses"]["200"]["schema"]["$ref"],
+                json!("#/definitions/Pet")
+            );
+            for name in ["Pet", "PetXml", "PetReport"] {
+                assert!(spec["definitions"][name].is_object());
+            }
+
+            #[cfg(feature = "v3")]
+            {
+                let resp = CLIENT
+                    .get(&format!("http://{}/api/spec/v3", addr))
+                    .send()
+                    .expect("request failed?");
+                let spec = resp.json::<serde_json::Value>().expect("json error");
+                let content = &spec["paths"]["/pets/{id}"]["get"]["responses"]["200"]["content"];
+                assert_eq!(
+                    content["application/json"]["schema"]["$ref"],
+                    json!("#/components/schemas/Pet")
+                );
+                assert_eq!(
+                    content["application/xml"]["schema"]["$ref"],
+                    json!("#/components/schemas/PetXml")
+                );
+                assert_eq!(
+                    content["text/csv"]["schema"]["$ref"],
+                    json!("#/components/schemas/PetReport")
+                );
+            }
+        },
+    );
+}
+
+#[test]
+fn test_rust_deprecated_fields() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[allow(deprecated)]
+    struct Owner {
+        /// Age of the owner
+        #[deprecated = "use `birthday` instead"]
+        age: Option<u8>,
+        #[deprecated(since = "1.2.0", note = "use `first_name` instead")]
+        name: Option<String>,
+        #[deprecated(since = "1.2.0")]
+        nickname: Option<String>,
+        first_name: String,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[allow(deprecated)]
+    enum Contact {
+        Email {
+            address: String,
+            #[deprecated]
+            verified: Option<bool>,
+        },
+    }
+
+    assert_eq!(
+        serde_json::to_value(Owner::raw_schema()).unwrap()["properties"],
+        json!({
+            "age": {
+                "deprecated": true,
+                "description": "Age of the owner\n\nDeprecated: use `birthday` instead",
+                "format": "int32",
+                "type": "integer"
+            },
+            "first_name": {
+                "type": "string"
+            },
+            "name": {
+                "deprecated": true,
+                "description": "Deprecated: use `first_name` instead",
+                "type": "string"
+            },
+            "nickname": {
+                "deprecated": true,
+                "type": "string"
+            }
+        })
+    );
+
+    let contact = serde_json::to_value(Contact::raw_schema()).unwrap();
+    assert_eq!(
+        contact["anyOf"][0]["properties"]["Email"]["properties"]["verified"],
+        json!({
+            "deprecated": true,
+            "type": "boolean"
+        })
+    );
+}
+
+#[test]
+fn test_date_format_with_pattern() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Booking {
+        #[openapi(format = "date", pattern = "^20[0-9]{2}-[0-9]{2}-[0-9]{2}$")]
+        check_in: String,
+        #[openapi(pattern = "^20[0-9]{2}-")]
+        check_out: chrono_dev::NaiveDate,
+    }
+
+    let schema = Booking::raw_schema();
+    assert_eq!(
+        serde_json::to_value(&schema).unwrap()["properties"],
+        json!({
+            "check_in": {
+                "format": "date",
+                "pattern": "^20[0-9]{2}-[0-9]{2}-[0-9]{2}$",
+                "type": "string"
+            },
+            "check_out": {
+                "format": "date",
+                "pattern": "^20[0-9]{2}-",
+                "type": "string"
+            }
+        })
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let schema: openapiv3::ReferenceOr<openapiv3::Schema> = schema.into();
+        assert_eq!(
+            serde_json::to_value(schema).unwrap()["properties"]["check_in"],
+            json!({
+                "format": "date",
+                "pattern": "^20[0-9]{2}-[0-9]{2}-[0-9]{2}$",
+                "type": "string"
+            })
+        );
+    }
+}
+
+#[test]
+fn test_enum_variant_priority() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[serde(tag = "kind")]
+    enum PaymentMethod {
+        #[openapi(priority = 2)]
+        Card {
+            last4: String,
+        },
+        #[openapi(priority = 1)]
+        BankAccount {
+            iban: String,
+        },
+        Cash,
+    }
+
+    let schema = serde_json::to_value(PaymentMethod::raw_schema()).unwrap();
+    let priorities = schema["anyOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|variant| variant["x_fp_priority"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(priorities, vec![json!(2), json!(1), json!(null)]);
+}
+
+#[test]
+fn test_collapsed_single_variant_enum() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[serde(tag = "type")]
+    #[openapi(collapsed)]
+    enum Event {
+        /// A pet was adopted.
+        Adopted { pet_id: u64 },
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[serde(untagged)]
+    #[openapi(collapsed)]
+    enum Identifier {
+        Name(String),
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[serde(untagged)]
+    enum Wrapped {
+        Name(String),
+    }
+
+    assert_eq!(
+        serde_json::to_value(Event::raw_schema()).unwrap(),
+        json!({
+            "description": "A pet was adopted.",
+            "properties": {
+                "pet_id": {
+                    "format": "int64",
+                    "type": "integer"
+                },
+                "type": {
+                    "enum": ["Adopted"],
+                    "type": "string"
+                }
+            },
+            "required": ["pet_id", "type"],
+            "type": "object"
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(Identifier::raw_schema()).unwrap(),
+        json!({ "type": "string" })
+    );
+    assert_eq!(
+        serde_json::to_value(Wrapped::raw_schema()).unwrap(),
+        json!({ "anyOf": [{ "type": "string" }] })
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_optional_query_struct() {
+    #[derive(Deserialize, Apiv2Schema)]
+    struct Filter {
+        name: String,
+        limit: Option<u16>,
+    }
+
+    #[api_v2_operation]
+    async fn list_pets(_filter: web::Query<Option<Filter>>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    #[api_v2_operation]
+    async fn search_pets(_filter: web::Query<Filter>) -> Result<NoContent, Error> {
+        unimplemented!()
+    }
+
+    let required = |op: paperclip::v2::models::DefaultOperationRaw| {
+        op.parameters
+            .into_iter()
+            .filter_map(|p| p.right().map(|p| (p.name.clone(), p.required)))
+            .collect::<BTreeMap<_, _>>()
+    };
+
+    assert_eq!(
+        required(paperclip_list_pets::raw_operation()),
+        vec![("limit".into(), false), ("name".into(), false)]
+            .into_iter()
+            .collect::<BTreeMap<String, bool>>()
+    );
+    assert_eq!(
+        required(paperclip_search_pets::raw_operation()),
+        vec![("limit".into(), false), ("name".into(), true)]
+            .into_iter()
+            .collect::<BTreeMap<String, bool>>()
+    );
+}
+
+#[test]
+#[cfg(feature = "v3")]
+fn test_operation_media_ranges_v3() {
+    use paperclip::v2::models::{
+        DefaultOperationRaw, DefaultPathItemRaw, DefaultResponseRaw, DefaultSchemaRaw, Either,
+        HttpMethod, MediaRange,
+    };
+
+    let mime = |s: &str| MediaRange(s.parse().unwrap());
+    let operation = |produces: Option<Vec<MediaRange>>| {
+        let mut op = DefaultOperationRaw {
+            produces: produces.map(|p| p.into_iter().collect()),
+            ..Default::default()
+        };
+        op.responses.insert(
+            "200".into(),
+            Either::Right(DefaultResponseRaw {
+                description: Some("OK".into()),
+                schema: Some(DefaultSchemaRaw {
+                    reference: Some("#/definitions/Pet".into()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+        );
+        op
+    };
+
+    let mut item = DefaultPathItemRaw::default();
+    item.methods
+        .insert(HttpMethod::Get, operation(Some(vec![mime("text/csv")])));
+    item.methods.insert(HttpMethod::Put, operation(None));
+    let mut api = DefaultApiRaw {
+        produces: std::iter::once(mime("application/xml")).collect(),
+        ..Default::default()
+    };
+    api.paths.insert("/pets".into(), item);
+
+    let spec = serde_json::to_value(paperclip::v3::openapiv2_to_v3(api.clone())).unwrap();
+    let content = |method: &str| {
+        let content = &spec["paths"]["/pets"][method]["responses"]["200"]["content"];
+        content
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    // The operation's own media ranges win over the ones of the spec.
+    assert_eq!(content("get"), vec!["text/csv"]);
+    assert_eq!(content("put"), vec!["application/xml"]);
+
+    // Without any media ranges, responses default to JSON.
+    api.produces.clear();
+    let spec = serde_json::to_value(paperclip::v3::openapiv2_to_v3(api)).unwrap();
+    let content = &spec["paths"]["/pets"]["put"]["responses"]["200"]["content"];
+    assert!(content["application/json"].is_object());
+}
+
+#[test]
+#[cfg(feature = "v3")]
+fn test_nullable_reference_v3() {
+    use paperclip::v2::models::DefaultSchemaRaw;
+
+    let mut pet = DefaultSchemaRaw {
+        data_type: Some(paperclip::v2::models::DataType::Object),
+        ..Default::default()
+    };
+    pet.properties.insert(
+        "owner".into(),
+        Box::new(DefaultSchemaRaw {
+            reference: Some("#/definitions/Owner".into()),
+            description: Some("Owner of the pet, if adopted".into()),
+            nullable: true,
+            ..Default::default()
+        }),
+    );
+    pet.properties.insert(
+        "breed".into(),
+        Box::new(DefaultSchemaRaw {
+            reference: Some("#/definitions/Breed".into()),
+            ..Default::default()
+        }),
+    );
+    pet.required.insert("breed".into());
+
+    let mut api = DefaultApiRaw::default();
+    api.definitions.insert("Pet".into(), pet);
+    let spec = serde_json::to_value(paperclip::v3::openapiv2_to_v3(api)).unwrap();
+    assert_eq!(
+        spec["components"]["schemas"]["Pet"]["properties"],
+        json!({
+            "breed": {
+                "$ref": "#/components/schemas/Breed"
+            },
+            "owner": {
+                "allOf": [{
+                    "$ref": "#/components/schemas/Owner"
+                }],
+                "description": "Owner of the pet, if adopted",
+                "nullable": true
+            }
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_default_error_schema() {
+    use actix_web::{error::ErrorBadRequest, HttpResponse, ResponseError};
+    use std::fmt;
+
+    /// Error returned by all the endpoints.
+    #[derive(Serialize, Apiv2Schema)]
+    struct ErrorBody {
+        message: String,
+    }
+
+    #[derive(Serialize, Apiv2Schema)]
+    struct Conflict {
+        existing: u64,
+    }
+
+    #[api_v2_errors(
+        400,
+        code = 409,
+        schema = "Conflict",
+        500,
+        description = "Internal Server Error"
+    )]
+    #[derive(Debug)]
+    struct PetError;
+
+    impl fmt::Display for PetError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "Bad Request")
+        }
+    }
+
+    impl ResponseError for PetError {
+        fn error_response(&self) -> HttpResponse {
+            HttpResponse::from_error(ErrorBadRequest("Bad Request"))
+        }
+    }
+
+    #[api_v2_operation]
+    async fn add_pet() -> Result<NoContent, PetError> {
+        Ok(NoContent)
+    }
+
+    #[api_v2_operation]
+    async fn remove_pet() -> Result<NoContent, PetError> {
+        Ok(NoContent)
+    }
+
+    run_and_check_app(
+        || {
+            App::new()
+                .wrap_api()
+                .with_json_spec_at("/api/spec")
+                .route("/pets", web::post().to(add_pet))
+                .default_error_schema::<ErrorBody>()
+                .route("/pets/{id}", web::delete().to(remove_pet))
+                .build()
+        },
+        |addr| {
+            let resp = CLIENT
+                .get(&format!("http://{}/api/spec", addr))
+                .send()
+                .expect("request failed?");
+            let spec = resp.json::<serde_json::Value>().expect("json error");
+            assert_eq!(
+                spec["definitions"]["ErrorBody"]["description"],
+                json!("Error returned by all the endpoints.")
+            );
+
+            // Operations added both before and after the registration use the default,
+            // unless the error has a schema of its own.
+            for (path, method) in [("/pets", "post"), ("/pets/{id}", "delete")] {
+                let responses = &spec["paths"][path][method]["responses"];
+                assert_eq!(
+                    responses["400"]["schema"],
+                    json!({"$ref": "#/definitions/ErrorBody"})
+                );
+                assert_eq!(
+                    responses["500"]["schema"],
+                    json!({"$ref": "#/definitions/ErrorBody"})
+                );
+                assert_eq!(
+                    responses["409"]["schema"],
+                    json!({"$ref": "#/definitions/Conflict"})
+                );
+                assert!(responses["204"].get("schema").is_none());
+            }
+        },
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_errors_variant_codes() {
+    use actix_web::{error::ErrorBadRequest, HttpResponse, ResponseError};
+    use std::fmt;
+
+    #[derive(Serialize, Apiv2Schema)]
+    struct Conflict {
+        existing: u64,
+    }
+
+    #[api_v2_errors(code = 500, description = "Internal Server Error")]
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum PetError {
+        #[api_v2_errors_variant(code = 404, description = "No such pet")]
+        NotFound,
+        #[api_v2_errors_variant(409, schema = "Conflict")]
+        AlreadyAdopted(u64),
+        Internal,
+    }
+
+    impl fmt::Display for PetError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "Bad Request")
+        }
+    }
+
+    impl ResponseError for PetError {
+        fn error_response(&self) -> HttpResponse {
+            HttpResponse::from_error(ErrorBadRequest("Bad Request"))
+        }
+    }
+
+    #[api_v2_operation]
+    async fn adopt_pet() -> Result<NoContent, PetError> {
+        Err(PetError::NotFound)
+    }
+
+    use paperclip::v2::schema::{Apiv2Errors, Apiv2Operation};
+    assert_eq!(
+        PetError::ERROR_MAP,
+        &[
+            (500, "Internal Server Error"),
+            (404, "No such pet"),
+            (409, "Conflict")
+        ]
+    );
+
+    let op = paperclip_adopt_pet::raw_operation();
+    let response = |code: &str| op.responses[code].right().unwrap().clone();
+    assert_eq!(response("404").description.as_deref(), Some("No such pet"));
+    assert_eq!(
+        response("409").schema.and_then(|s| s.reference),
+        Some("#/definitions/Conflict".into())
+    );
+    assert_eq!(
+        response("500").description.as_deref(),
+        Some("Internal Server Error")
+    );
+    assert!(paperclip_adopt_pet::definitions().contains_key("Conflict"));
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_derived_response_error() {
+    use actix_web::{http::StatusCode, ResponseError};
+    use paperclip::actix::Apiv2ResponseError;
+
+    #[api_v2_errors(code = 500)]
+    #[derive(Debug, Apiv2ResponseError)]
+    #[allow(dead_code)]
+    enum PetError {
+        #[api_v2_errors_variant(code = 404, description = "No such pet")]
+        NotFound,
+        #[api_v2_errors_variant(409)]
+        AlreadyAdopted(u64),
+        #[api_v2_errors_variant(code = 422, description = "Invalid pet name")]
+        InvalidName {
+            name: String,
+        },
+        Internal,
+    }
+
+    let cases = [
+        (PetError::NotFound, StatusCode::NOT_FOUND, "No such pet"),
+        (
+            PetError::AlreadyAdopted(7),
+            StatusCode::CONFLICT,
+            "Conflict",
+        ),
+        (
+            PetError::InvalidName { name: "".into() },
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Invalid pet name",
+        ),
+        (
+            PetError::Internal,
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Internal Server Error",
+        ),
+    ];
+    for (error, status, message) in cases {
+        assert_eq!(error.status_code(), status);
+        assert_eq!(error.to_string(), message);
+        assert_eq!(error.error_response().status(), status);
+    }
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_error_content_type() {
+    use paperclip::{
+        actix::Apiv2ResponseError,
+        v2::models::{DefaultPathItemRaw, HttpMethod},
+    };
+
+    #[derive(Serialize, Apiv2Schema)]
+    struct Problem {
+        title: String,
+        status: u16,
+    }
+
+    #[api_v2_errors(
+        code = 400,
+        schema = "Problem",
+        content = "application/problem+json",
+        code = 500
+    )]
+    #[derive(Debug, Apiv2ResponseError)]
+    #[allow(dead_code)]
+    enum PetError {
+        Internal,
+    }
+
+    #[api_v2_operation]
+    async fn adopt_pet() -> Result<web::Json<String>, PetError> {
+        Ok(web::Json("Rex".into()))
+    }
+
+    let op = paperclip_adopt_pet::raw_operation();
+    let produces = |code: &str| {
+        op.responses[code]
+            .right()
+            .unwrap()
+            .produces
+            .as_ref()
+            .map(|p| p.iter().map(|m| m.0.to_string()).collect::<Vec<_>>())
+    };
+    assert_eq!(
+        produces("400"),
+        Some(vec!["application/problem+json".into()])
+    );
+    assert_eq!(produces("500"), None);
+
+    let mut item = DefaultPathItemRaw::default();
+    item.methods.insert(HttpMethod::Post, op);
+    let mut api = DefaultApiRaw::default();
+    api.paths.insert("/pets".into(), item);
+    // Swagger 2.0 only has media types for the whole operation.
+    let spec = serde_json::to_value(&api).unwrap();
+    assert_eq!(
+        spec["paths"]["/pets"]["post"]["responses"]["400"]["schema"],
+        json!({"$ref": "#/definitions/Problem"})
+    );
+
+    #[cfg(feature = "v3")]
+    {
+        let spec = serde_json::to_value(paperclip::v3::openapiv2_to_v3(api)).unwrap();
+        let responses = &spec["paths"]["/pets"]["post"]["responses"];
+        assert_eq!(
+            responses["400"]["content"],
+            json!({
+                "application/problem+json": {
+                    "schema": {"$ref": "#/components/schemas/Problem"}
+                }
+            })
+        );
+        assert!(responses["200"]["content"]["application/json"].is_object());
+    }
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_problem_details_schema() {
+    use paperclip::actix::{Apiv2ResponseError, ProblemDetails};
+
+    #[api_v2_errors(
+        code = 400,
+        schema = "ProblemDetails",
+        content = "application/problem+json"
+    )]
+    #[derive(Debug, Apiv2ResponseError)]
+    #[allow(dead_code)]
+    enum PetError {
+        Internal,
+    }
+
+    #[api_v2_operation]
+    async fn adopt_pet() -> Result<NoContent, PetError> {
+        Ok(NoContent)
+    }
+
+    run_and_check_app(
+        || {
+            App::new()
+                .wrap_api()
+                .with_json_spec_at("/api/spec")
+                .route("/pets", web::post().to(adopt_pet))
+                .build()
+        },
+        |addr| {
+            let resp = CLIENT
+                .get(&format!("http://{}/api/spec", addr))
+                .send()
+                .expect("request failed?");
+            let spec = resp.json::<serde_json::Value>().expect("json error");
+            assert_eq!(
+                spec["paths"]["/pets"]["post"]["responses"]["400"]["schema"],
+                json!({"$ref": "#/definitions/ProblemDetails"})
+            );
+            assert_eq!(
+                spec["definitions"]["ProblemDetails"],
+                json!({
+                    "description": "Problem details of an error response (RFC 7807).",
+                    "type": "object",
+                    "properties": {
+                        "detail": {
+                            "description": "Human-readable explanation specific to this occurrence of the problem.",
+                            "type": "string"
+                        },
+                        "instance": {
+                            "description": "URI reference identifying this occurrence of the problem.",
+                            "type": "string"
+                        },
+                        "status": {
+                            "description": "HTTP status code of this occurrence of the problem.",
+                            "type": "integer"
+                        },
+                        "title": {
+                            "description": "Short, human-readable summary of the problem type.",
+                            "type": "string"
+                        },
+                        "type": {
+                            "description": "URI reference identifying the problem type.",
+                            "type": "string"
+                        }
+                    }
+                })
+            );
+        },
+    );
+
+    let problem = ProblemDetails {
+        title: Some("Bad Request".into()),
+        status: Some(400),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(problem).unwrap(),
+        json!({"title": "Bad Request", "status": 400})
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_skip_if_empty_schema() {
+    use paperclip::v2::schema::{Apiv2Operation, Apiv2Schema};
+
+    /// Marks the pet as adoptable.
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[openapi(skip_if_empty)]
+    struct Adoptable {}
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[openapi(skip_if_empty)]
+    struct Unit;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[openapi(skip_if_empty)]
+    struct Owner {
+        name: String,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    #[openapi(skip_if_empty)]
+    struct Cached {
+        #[serde(skip)]
+        #[allow(dead_code)]
+        hits: u32,
+    }
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    struct Pet {
+        adoptable: Adoptable,
+        unit: Unit,
+        owner: Owner,
+        cached: Cached,
+    }
+
+    #[api_v2_operation]
+    async fn adopt_pet(_marker: web::Json<Adoptable>, pet: web::Json<Pet>) -> web::Json<Pet> {
+        pet
+    }
+
+    assert_eq!(Adoptable::name(), None);
+    assert_eq!(Owner::name(), Some("Owner".into()));
+
+    assert_eq!(
+        paperclip_adopt_pet::definitions()
+            .keys()
+            .collect::<Vec<_>>(),
+        vec!["Pet"]
+    );
+    let definitions = Pet::all_definitions();
+    assert_eq!(definitions.keys().collect::<Vec<_>>(), vec!["Owner", "Pet"]);
+    let pet = serde_json::to_value(&definitions["Pet"]).unwrap();
+    assert_eq!(pet["properties"]["adoptable"], json!({}));
+    assert_eq!(pet["properties"]["unit"], json!({}));
+    assert_eq!(pet["properties"]["cached"], json!({}));
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_http_request_extractor_ignored() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[derive(Deserialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    struct Filter {
+        name: String,
+    }
+
+    #[api_v2_operation]
+    async fn get_pet(_req: HttpRequest, _filter: web::Query<Filter>) -> NoContent {
+        NoContent
+    }
+
+    let op = paperclip_get_pet::raw_operation();
+    let parameters = op
+        .parameters
+        .iter()
+        .filter_map(|p| p.right().map(|p| p.name.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(parameters, vec!["name".to_owned()]);
+    assert!(!paperclip_get_pet::definitions().contains_key("HttpRequest"));
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_app_data_extractor_ignored() {
+    use paperclip::v2::schema::Apiv2Operation;
+
+    #[allow(dead_code)]
+    struct Pool {
+        size: usize,
+    }
+
+    #[api_v2_operation]
+    async fn list_pets(_pool: web::Data<Pool>) -> NoContent {
+        NoContent
+    }
+
+    let op = paperclip_list_pets::raw_operation();
+    assert!(op.parameters.is_empty());
+    assert!(paperclip_list_pets::definitions().is_empty());
+
+    run_and_check_app(
+        || {
+            App::new()
+                .app_data(web::Data::new(Pool { size: 4 }))
+                .wrap_api()
+                .with_json_spec_at("/api/spec")
+                .route("/pets", web::get().to(list_pets))
+                .build()
+        },
+        |addr| {
+            let resp = CLIENT
+                .get(&format!("http://{}/api/spec", addr))
+                .send()
+                .expect("request failed?");
+            let spec = resp.json::<serde_json::Value>().expect("json error");
+            assert!(spec["paths"]["/pets"]["get"].get("parameters").is_none());
+            assert_eq!(spec["definitions"], json!({}));
+        },
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_custom_extractor_modifier() {
+    use paperclip::{
+        actix::OperationModifier,
+        v2::{
+            models::{DataType, DefaultOperationRaw, Either, Parameter, ParameterIn},
+            schema::Apiv2Schema,
+        },
+    };
+
+    /// Extracts the tenant from the `X-Tenant-Id` header.
+    struct Tenant(String);
+
+    impl FromRequest for Tenant {
+        type Error = Error;
+        type Future = Ready<Result<Self, Self::Error>>;
+
+        fn from_request(req: &HttpRequest, _payload: &mut actix_web::dev::Payload) -> Self::Future {
+            let tenant = req
+                .headers()
+                .get("X-Tenant-Id")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default();
+            ready(Ok(Tenant(tenant.into())))
+        }
+    }
+
+    impl Apiv2Schema for Tenant {}
+
+    impl OperationModifier for Tenant {
+        fn update_parameter(op: &mut DefaultOperationRaw) {
+            op.parameters.push(Either::Right(Parameter {
+                in_: ParameterIn::Header,
+                name: "X-Tenant-Id".into(),
+                description: Some("Tenant owning the pets".into()),
+                required: true,
+                data_type: Some(DataType::String),
+                ..Default::default()
+            }));
+        }
+    }
+
+    #[api_v2_operation]
+    async fn list_pets(tenant: Tenant) -> web::Json<String> {
+        web::Json(tenant.0)
+    }
+
+    run_and_check_app(
+        || {
+            App::new()
+                .wrap_api()
+                .with_json_spec_at("/api/spec")
+                .route("/pets", web::get().to(list_pets))
+                .build()
+        },
+        |addr| {
+            let resp = CLIENT
+                .get(&format!("http://{}/pets", addr))
+                .header("X-Tenant-Id", "acme")
+                .send()
+                .expect("request failed?");
+            assert_eq!(resp.json::<String>().expect("json error"), "acme");
+
+            let resp = CLIENT
+                .get(&format!("http://{}/api/spec", addr))
+                .send()
+                .expect("request failed?");
+            let spec = resp.json::<serde_json::Value>().expect("json error");
+            assert_eq!(
+                spec["paths"]["/pets"]["get"]["parameters"],
+                json!([{
+                    "description": "Tenant owning the pets",
+                    "in": "header",
+                    "name": "X-Tenant-Id",
+                    "required": true,
+                    "type": "string"
+                }])
+            );
+        },
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_deprecated_if() {
+    #[api_v2_operation(deprecated_if = "feature = \"actix4\"")]
+    async fn list_pets() -> NoContent {
+        NoContent
+    }
+
+    #[api_v2_operation(deprecated_if = "not(feature = \"actix4\")")]
+    async fn get_pet() -> NoContent {
+        NoContent
+    }
+
+    #[api_v2_operation(deprecated, summary = "Adopt a pet")]
+    async fn adopt_pet() -> NoContent {
+        NoContent
+    }
+
+    assert!(paperclip_list_pets::raw_operation().deprecated);
+    assert!(!paperclip_get_pet::raw_operation().deprecated);
+    let op = paperclip_adopt_pet::raw_operation();
+    assert!(op.deprecated);
+    assert_eq!(op.summary.as_deref(), Some("Adopt a pet"));
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_no_content_response_headers() {
+    #[api_v2_operation(no_content, headers("Location" = "URL of the moved pet"))]
+    async fn move_pet() -> actix_web::HttpResponse {
+        unimplemented!()
+    }
+
+    assert_eq!(
+        serde_json::to_value(paperclip_move_pet::raw_operation().responses).unwrap(),
+        json!({
+            "204": {
+                "description": "No Content",
+                "headers": {
+                    "Location": {
+                        "description": "URL of the moved pet",
+                        "type": "string"
+                    }
+                }
+            }
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_response_header_types() {
+    #[api_v2_operation(
+        response(200, description = "Pet moved"),
+        headers(
+            "Location" = "URL of the moved pet",
+            "X-Rate-Limit" = "Remaining requests"
+        ),
+        header_types("X-Rate-Limit" = "u32")
+    )]
+    async fn move_pet() -> actix_web::HttpResponse {
+        unimplemented!()
+    }
+
+    assert_eq!(
+        serde_json::to_value(paperclip_move_pet::raw_operation().responses).unwrap(),
+        json!({
+            "200": {
+                "description": "Pet moved",
+                "headers": {
+                    "Location": {
+                        "description": "URL of the moved pet",
+                        "type": "string"
+                    },
+                    "X-Rate-Limit": {
+                        "description": "Remaining requests",
+                        "format": "int32",
+                        "type": "integer"
+                    }
+                }
+            }
+        })
+    );
+}
+
+#[test]
+#[cfg(all(feature = "actix4", feature = "v3"))]
+fn test_deep_object_query_parameter() {
+    use paperclip::v2::models::{DefaultPathItemRaw, Either, HttpMethod, ParameterStyle};
+
+    #[derive(Deserialize, Apiv2Schema)]
+    struct PetFilter {
+        /// Name of the pets
+        name: Option<String>,
+        /// Minimum age of the pets
+        min_age: Option<u32>,
+    }
+
+    #[derive(Deserialize, Apiv2Schema)]
+    struct PetQuery {
+        #[openapi(style = "deepObject")]
+        filter: PetFilter,
+        limit: Option<u32>,
+    }
+
+    #[api_v2_operation]
+    async fn list_pets(_query: web::Query<PetQuery>) -> NoContent {
+        NoContent
+    }
+
+    let op = paperclip_list_pets::raw_operation();
+    let param = |name: &str| match op
+        .parameters
+        .iter()
+        .find(|p| p.right().unwrap().name == name)
+    {
+        Some(Either::Right(param)) => param.clone(),
+        _ => panic!("missing parameter {}", name),
+    };
+    let filter = param("filter");
+    assert_eq!(filter.style, Some(ParameterStyle::DeepObject));
+    assert!(filter
+        .object_schema
+        .as_ref()
+        .unwrap()
+        .properties
+        .contains_key("min_age"));
+    // Query parameters don't have a schema in v2.
+    assert!(serde_json::to_value(&filter)
+        .unwrap()
+        .get("schema")
+        .is_none());
+    assert_eq!(param("limit").style, None);
+
+    let mut item = DefaultPathItemRaw::default();
+    item.methods.insert(HttpMethod::Get, op);
+    let mut api = DefaultApiRaw::default();
+    api.paths.insert("/pets".into(), item);
+    let spec = serde_json::to_value(paperclip::v3::openapiv2_to_v3(api)).unwrap();
+    let params = spec["paths"]["/pets"]["get"]["parameters"]
+        .as_array()
+        .unwrap();
+    let filter = params.iter().find(|p| p["name"] == "filter").unwrap();
+    assert_eq!(filter["in"], "query");
+    assert_eq!(filter["style"], "deepObject");
+    assert_eq!(filter["explode"], true);
+    assert_eq!(
+        filter["schema"]["properties"]["min_age"],
+        json!({
+            "description": "Minimum age of the pets",
+            "format": "int32",
+            "type": "integer"
+        })
+    );
+    let limit = params.iter().find(|p| p["name"] == "limit").unwrap();
+    assert_eq!(limit["schema"]["type"], "integer");
+    assert!(limit.get("explode").is_none());
+}
+
+#[test]
+fn test_optional_bool() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Apiv2Schema)]
+    #[allow(dead_code)]
+    struct PetFilter {
+        /// Only (un)vaccinated pets when `true` (`false`), or all pets when absent.
+        vaccinated: Option<bool>,
+        adopted: bool,
+    }
+
+    let schema = PetFilter::raw_schema();
+    assert_eq!(
+        serde_json::to_value(&schema).unwrap()["properties"]["vaccinated"],
+        json!({
+            "description": "Only (un)vaccinated pets when `true` (`false`), or all pets when absent.",
+            "type": "boolean"
+        })
+    );
+    assert!(!schema.required.contains("vaccinated"));
+    assert!(schema.required.contains("adopted"));
+}
+
+#[test]
+fn test_example_from_default() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Serialize, Apiv2Schema)]
+    #[openapi(example_from_default)]
+    struct Pagination {
+        page: u32,
+        per_page: u32,
+        sort: Option<String>,
+    }
+
+    impl Default for Pagination {
+        fn default() -> Self {
+            Pagination {
+                page: 1,
+                per_page: 20,
+                sort: Some("name".into()),
+            }
+        }
+    }
+
+    assert_eq!(
+        Pagination::raw_schema().example,
+        Some(serde_json::to_value(Pagination::default()).unwrap())
+    );
+    assert_eq!(
+        Pagination::raw_schema().example,
+        Some(json!({
+            "page": 1,
+            "per_page": 20,
+            "sort": "name"
+        }))
+    );
+}
+
+#[test]
+#[cfg(feature = "actix4")]
+fn test_shared_parameter() {
+    use paperclip::{
+        actix::OperationModifier,
+        v2::{
+            models::{DataType, DefaultOperationRaw, Either, Parameter, ParameterIn},
+            schema::Apiv2Schema,
+        },
+    };
+
+    fn tenant_header() -> Parameter<paperclip::v2::models::DefaultSchemaRaw> {
+        Parameter {
+            in_: ParameterIn::Header,
+            name: "X-Tenant-Id".into(),
+            description: Some("Tenant owning the pets".into()),
+            required: true,
+            data_type: Some(DataType::String),
+            ..Default::default()
+        }
+    }
+
+    struct Tenant;
+
+    impl FromRequest for Tenant {
+        type Error = Error;
+        type Future = Ready<Result<Self, Self::Error>>;
+
+        fn from_request(_: &HttpRequest, _payload: &mut actix_web::dev::Payload) -> Self::Future {
+            ready(Ok(Tenant))
+        }
+    }
+
+    impl Apiv2Schema for Tenant {}
+
+    impl OperationModifier for Tenant {
+        fn update_parameter(op: &mut DefaultOperationRaw) {
+            op.parameters.push(Either::Right(tenant_header()));
+        }
+    }
+
+    #[api_v2_operation]
+    async fn list_pets(_tenant: Tenant, _limit: web::Query<PetQuery>) -> NoContent {
+        NoContent
+    }
+
+    #[api_v2_operation]
+    async fn remove_pet(_tenant: Tenant) -> NoContent {
+        NoContent
+    }
+
+    #[derive(Deserialize, Apiv2Schema)]
+    struct PetQuery {
+        #[allow(dead_code)]
+        limit: Option<u32>,
+    }
+
+    run_and_check_app(
+        || {
+            let app = App::new()
+                .wrap_api()
+                .with_json_spec_at("/api/spec")
+                .route("/pets", web::get().to(list_pets))
+                .shared_parameter("TenantId", tenant_header())
+                .route("/pets/{id}", web::delete().to(remove_pet));
+            #[cfg(feature = "v3")]
+            let app = app.with_json_spec_v3_at("/api/spec/v3");
+            app.build()
+        },
+        |addr| {
+            let resp = CLIENT
+                .get(&format!("http://{}/api/spec", addr))
+                .send()
+                .expect("request failed?");
+            let spec = resp.json::<serde_json::Value>().expect("json error");
+            assert_eq!(
+                spec["parameters"],
+                json!({
+                    "TenantId": {
+                        "description": "Tenant owning the pets",
+                        "in": "header",
+                        "name": "X-Tenant-Id",
+                        "required": true,
+                        "type": "string"
+                    }
+                })
+            );
+            // Operations added both before and after the registration reference it.
+            assert_eq!(
+                spec["paths"]["/pets"]["get"]["parameters"],
+                json!([
+                    {"$ref": "#/parameters/TenantId"},
+                    {
+                        "format": "int32",
+                        "in": "query",
+                        "name": "limit",
+                        "type": "integer"
+                    }
+                ])
+            );
+            assert_eq!(
+                spec["paths"]["/pets/{id}"]["delete"]["parameters"],
+                json!([{"$ref": "#/parameters/TenantId"}])
+            );
+
+            #[cfg(feature = "v3")]
+            {
+                let resp = CLIENT
+                    .get(&format!("http://{}/api/spec/v3", addr))
+                    .send()
+                    .expect("request failed?");
+                let spec = resp.json::<serde_json::Value>().expect("json error");
+                assert_eq!(
+                    spec["components"]["parameters"]["TenantId"],
+                    json!({
+                        "description": "Tenant owning the pets",
+                        "in": "header",
+                        "name": "X-Tenant-Id",
+                        "required": true,
+                        "schema": {
+                            "type": "string"
+                        },
+                        "style": "simple"
+                    })
+                );
+                assert_eq!(
+                    spec["paths"]["/pets/{id}"]["delete"]["parameters"],
+                    json!([{"$ref": "#/components/parameters/TenantId"}])
+                );
+            }
+        },
+    );
+}
+
+#[test]
+#[cfg(all(feature = "option-nullable", feature = "v3"))]
+fn test_option_nullable_reference_v3() {
+    use paperclip::v2::schema::Apiv2Schema;
+
+    #[derive(Deserialize, Serialize, Apiv2Schema)]
+    enum Filter {
+        Name { value: String },
+        Not { filter: Option<Box<Filter>> },
+    }
+
+    let schema: openapiv3::ReferenceOr<openapiv3::Schema> = Filter::raw_schema().into();
+    let schema = serde_json::to_value(schema).unwrap();
+    // Recursive types are referenced, and the reference is wrapped (once) to be nullable.
+    assert_eq!(
+        schema["anyOf"][1]["properties"]["Not"]["properties"]["filter"],
+        json!({
+            "allOf": [{
+                "$ref": "#/components/schemas/Filter"
+            }],
+            "nullable": true
+        })
+    );
+}
+
+#[test]
+#[cfg(all(feature = "option-nullable", feature = "v3"))]
+fn test_option_nullable_all_of_reference_v3() {
+    use paperclip::v2::models::DefaultSchemaRaw;
+
+    // References which are already wrapped to be nullable are left alone.
+    let schema = DefaultSchemaRaw {
+        all_of: vec![Box::new(DefaultSchemaRaw {
+            reference: Some("#/definitions/Owner".into()),
+            nullable: true,
+            ..Default::default()
+        })],
+        nullable: true,
+        ..Default::default()
+    };
+    let schema: openapiv3::ReferenceOr<openapiv3::Schema> = schema.into();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        json!({
+            "allOf": [{
+                "$ref": "#/components/schemas/Owner"
+            }],
+            "nullable": true
+        })
+    );
+}
+
+#[test]
+fn test_shared_body_parameter_not_referenced() {
+    use paperclip::v2::models::{
+        DefaultOperationRaw, DefaultPathItemRaw, DefaultSchemaRaw, Either, HttpMethod, Parameter,
+        ParameterIn,
+    };
+
+    let body = || Parameter {
+        in_: ParameterIn::Body,
+        name: "body".into(),
+        required: true,
+        schema: Some(DefaultSchemaRaw {
+            reference: Some("#/definitions/Pet".into()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let mut op = DefaultOperationRaw::default();
+    op.parameters.push(Either::Right(body()));
+    let mut item = DefaultPathItemRaw::default();
+    item.methods.insert(HttpMethod::Post, op);
+    let mut api = DefaultApiRaw::default();
+    api.paths.insert("/pets".into(), item);
+    api.parameters.insert("PetBody".into(), body());
+
+    // Body parameters can't be shared in v3, so they stay inline.
+    api.reference_shared_parameters();
+    let op = &api.paths["/pets"].methods[&HttpMethod::Post];
+    assert!(matches!(&op.parameters[..], [Either::Right(p)] if p.in_ == ParameterIn::Body));
+
+    #[cfg(feature = "v3")]
+    {
+        let spec = serde_json::to_value(paperclip::v3::openapiv2_to_v3(api)).unwrap();
+        let op = &spec["paths"]["/pets"]["post"];
+        assert!(op.get("parameters").is_none());
+        assert_eq!(
+            op["requestBody"]["content"]["application/json"]["schema"],
+            json!({"$ref": "#/components/schemas/Pet"})
+        );
+    }
+}